@@ -11,16 +11,42 @@ class FastBrowser
     lib_file = "libfast_browser.#{FFI::Platform::LIBSUFFIX}"
     ffi_lib File.expand_path("../../ext/fast_browser/#{lib_file}", __FILE__)
 
-    %w(chrome edge firefox opera safari bot).each do |tester|
+    %w(chrome edge firefox opera safari bot ai_crawler link_preview heuristic_bot library threat).each do |tester|
       attach_function "is_#{tester}".to_sym, [:pointer], :bool
     end
 
     attach_function :get_browser_minor_version, [:pointer], :int8
     attach_function :get_browser_major_version, [:pointer], :int8
     attach_function :is_mobile, [:pointer], :bool
+    attach_function :get_spoof_likelihood, [:pointer], :float
+    attach_function :get_traffic_score, [:pointer], :float
+    attach_function :get_app_scale, [:pointer], :float
+    attach_function :get_display_scale, [:pointer], :float
+    attach_function :get_display_viewport_width, [:pointer], :uint32
+    attach_function :get_display_width, [:pointer], :uint32
+    attach_function :apply_client_hint, [:pointer, :string, :string], :void
+    attach_function :apply_max_touch_points, [:pointer, :uint32], :void
+    attach_function :get_platform_bitness, [:pointer], :uint8
+    attach_function :apply_architecture_hints, [:pointer, :string, :string], :void
+    attach_function :get_library_minor_version, [:pointer], :int8
+    attach_function :get_library_major_version, [:pointer], :int8
+    attach_function :register_app_format, [:string, :string, :string], :bool
 
+    attach_string_returning_function :get_app_model, [:pointer]
+    attach_string_returning_function :get_app_name, [:pointer]
+    attach_string_returning_function :get_app_version, [:pointer]
     attach_string_returning_function :get_bot_name, [:pointer]
+    attach_string_returning_function :get_bot_category, [:pointer]
     attach_string_returning_function :get_browser_family, [:pointer]
+    attach_string_returning_function :get_device_brand, [:pointer]
+    attach_string_returning_function :get_device_chip, [:pointer]
+    attach_string_returning_function :get_device_name, [:pointer]
+    attach_string_returning_function :get_library_name, [:pointer]
+    attach_string_returning_function :get_locale, [:pointer, :string]
+    attach_string_returning_function :get_platform_architecture, [:pointer]
+    attach_string_returning_function :get_platform_display_name, [:pointer]
+    attach_string_returning_function :get_threats, [:pointer]
+    attach_string_returning_function :get_traffic_verdict, [:pointer]
     attach_string_returning_function :get_user_agent, [:pointer]
     attach_string_returning_function :get_version, []
 
@@ -50,6 +76,15 @@ class FastBrowser
     @pointer = RustLib.parse_user_agent(string)
   end
 
+  # Teach the parser an app's own user agent format; it applies to every
+  # FastBrowser created afterwards. `pattern` is a Rust regex with a `name`
+  # group and optional `version`, `model`, `scale` and `os_version` groups, and
+  # `os` is a platform such as 'IOS' or 'Android'.
+  def self.register_app_format(id, pattern, os = nil)
+    RustLib.register_app_format(id, pattern, os) or
+      raise ArgumentError, "invalid app format #{id}"
+  end
+
   # Boolean methods
   def bot?;     RustLib.is_bot(@pointer)     end
   def chrome?;  RustLib.is_chrome(@pointer)  end
@@ -59,10 +94,36 @@ class FastBrowser
   def safari?;  RustLib.is_safari(@pointer)  end
   def mobile?;  RustLib.is_mobile(@pointer)  end
 
+  # Bot-related methods
+  def ai_crawler?;    RustLib.is_ai_crawler(@pointer)    end
+  def bot_category;   RustLib.get_bot_category(@pointer)   end
+  def heuristic_bot?; RustLib.is_heuristic_bot(@pointer) end
+  def link_preview?;  RustLib.is_link_preview(@pointer)  end
+
   # General methods
   def bot_name;   RustLib.get_bot_name(@pointer)   end
   def user_agent; RustLib.get_user_agent(@pointer) end
 
+  # BCP 47 locale (eg. "ko-KR") from the user agent and, if given, the
+  # Accept-Language header; nil if neither has one
+  def locale(accept_language = nil)
+    locale = RustLib.get_locale(@pointer, accept_language)
+    locale.empty? ? nil : locale
+  end
+
+  # Chance (0.0 to 1.0) that the user agent is spoofed
+  def spoof_likelihood; RustLib.get_spoof_likelihood(@pointer) end
+
+  # Attack payloads (Log4Shell, Shellshock, SQL injection, etc.) in the user agent
+  def threat?; RustLib.is_threat(@pointer) end
+  def threats; RustLib.get_threats(@pointer).split(',') end
+
+  # Human, KnownGoodBot, ClaimedBot, KnownBadBot, Automation or Unknown, and
+  # how likely (0.0 to 1.0) the request is automated. Catalog bots are
+  # ClaimedBot here, since only the user agent is checked.
+  def traffic_verdict; RustLib.get_traffic_verdict(@pointer) end
+  def traffic_score;   RustLib.get_traffic_score(@pointer)   end
+
   # Browser-related methods
   def browser_family;        RustLib.get_browser_family(@pointer)        end
   def browser_major_version; RustLib.get_browser_major_version(@pointer) end
@@ -71,6 +132,56 @@ class FastBrowser
   alias_method :family,        :browser_family
   alias_method :major_version, :browser_major_version
   alias_method :minor_version, :browser_minor_version
+
+  # Platform-related methods
+  def architecture;  RustLib.get_platform_architecture(@pointer) end
+  def bitness;       RustLib.get_platform_bitness(@pointer)      end
+  def platform_name; RustLib.get_platform_display_name(@pointer) end
+
+  # Refine the architecture with the Sec-CH-UA-Arch and Sec-CH-UA-Bitness
+  # client hints; either may be nil
+  def apply_architecture_hints(arch, bitness)
+    RustLib.apply_architecture_hints(@pointer, arch, bitness)
+    self
+  end
+
+  # Native app-related methods
+  def app_model;   RustLib.get_app_model(@pointer)   end
+  def app_name;    RustLib.get_app_name(@pointer)    end
+  def app_scale;   RustLib.get_app_scale(@pointer)   end
+  def app_version; RustLib.get_app_version(@pointer) end
+
+  # Device-related methods, from the bundled device database
+  def device_brand; RustLib.get_device_brand(@pointer) end
+  def device_chip;  RustLib.get_device_chip(@pointer)  end
+  def device_name;  RustLib.get_device_name(@pointer)  end
+
+  # Display-related methods; scale is device pixels per CSS pixel, the
+  # viewport width is in CSS pixels and the width (from Sec-CH-Width) is in
+  # device pixels (0 when unknown)
+  def scale;          RustLib.get_display_scale(@pointer)          end
+  def viewport_width; RustLib.get_display_viewport_width(@pointer) end
+  def width;          RustLib.get_display_width(@pointer)          end
+
+  # Apply a client hint header such as Sec-CH-DPR or Sec-CH-Viewport-Width
+  def apply_client_hint(name, value)
+    RustLib.apply_client_hint(@pointer, name, value)
+    self
+  end
+
+  # Apply what the page collected: navigator.maxTouchPoints (a touchscreen
+  # "Mac" is an iPad in desktop mode) and a hash of client hint headers
+  def apply_client_signals(max_touch_points: nil, client_hints: {})
+    RustLib.apply_max_touch_points(@pointer, max_touch_points) if max_touch_points
+    client_hints.each { |name, value| apply_client_hint(name, value) }
+    self
+  end
+
+  # HTTP client library-related methods
+  def library?;              RustLib.is_library(@pointer)                end
+  def library_name;          RustLib.get_library_name(@pointer)          end
+  def library_major_version; RustLib.get_library_major_version(@pointer) end
+  def library_minor_version; RustLib.get_library_minor_version(@pointer) end
 end
 
 if FastBrowser::RustLib.get_version != FastBrowser::VERSION
@@ -0,0 +1,104 @@
+// Generates the lookup tables for the device databases in `data/`; see `src/android_models.rs`
+// and `src/apple_devices.rs`.
+
+use std::env;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+
+#[path = "src/phf.rs"]
+mod phf;
+
+/// Each database in `data/` and the file its table is generated into
+const TABLES: [(&str, &str); 2] = [
+    ("data/android_models.tsv", "android_models.rs"),
+    ("data/apple_devices.tsv",  "apple_devices.rs"),
+];
+
+const FORM_FACTORS: [&str; 5] = ["Desktop", "Phone", "Tablet", "Tv", "Wearable"];
+
+struct Row {
+    code: String,
+    brand: String,
+    marketing_name: String,
+    form_factor: String,
+    chip: Option<String>,
+    scale: Option<f32>,
+    viewport_width: Option<u32>,
+    release_year: u16,
+}
+
+fn parse(path: &str, table: &str) -> Vec<Row> {
+    let mut rows: Vec<Row> = vec![];
+
+    for (index, line) in table.lines().enumerate() {
+        if line.trim().is_empty() || line.starts_with('#') { continue }
+
+        let fail = |message: &str| -> ! {
+            panic!("{} line {}: {}", path, index + 1, message)
+        };
+
+        let columns: Vec<&str> = line.split('\t').collect();
+        if columns.len() != 8 { fail("expected 8 tab-separated columns") }
+        if !FORM_FACTORS.contains(&columns[3]) { fail("unknown form factor") }
+
+        let density = match columns[5] {
+            "-"   => None,
+            scale => Some(scale.parse::<f32>().ok().filter(|&scale| scale > 0.0).unwrap_or_else(|| fail("invalid scale"))),
+        };
+        let viewport = match columns[6] {
+            "-"   => None,
+            width => Some(width.parse::<u32>().unwrap_or_else(|_| fail("invalid viewport width"))),
+        };
+        let year = columns[7].parse().unwrap_or_else(|_| fail("invalid release year"));
+        if rows.iter().any(|row| row.code.eq_ignore_ascii_case(columns[0])) { fail("duplicate model code") }
+
+        rows.push(Row {
+            code:           columns[0].to_owned(),
+            brand:          columns[1].to_owned(),
+            marketing_name: columns[2].to_owned(),
+            form_factor:    columns[3].to_owned(),
+            chip:           if columns[4] == "-" { None } else { Some(columns[4].to_owned()) },
+            scale:          density,
+            viewport_width: viewport,
+            release_year:   year,
+        });
+    }
+
+    if rows.is_empty() { panic!("{} is empty", path) }
+    rows
+}
+
+fn generate(path: &str, out_file: &str) {
+    println!("cargo:rerun-if-changed={}", path);
+
+    let mut table = String::new();
+    File::open(path).and_then(|mut file| file.read_to_string(&mut table)).unwrap();
+
+    let rows = parse(path, &table);
+    let keys: Vec<String> = rows.iter().map(|row| row.code.clone()).collect();
+    let (seeds, slots) = phf::generate(&keys);
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join(out_file);
+    let mut out = File::create(out_path).unwrap();
+
+    writeln!(out, "static SEEDS: [u32; {}] = {:?};", seeds.len(), seeds).unwrap();
+    writeln!(out, "static MODELS: [(&str, ModelInfo); {}] = [", slots.len()).unwrap();
+    for index in slots {
+        let row = &rows[index];
+        writeln!(
+            out,
+            "    ({:?}, ModelInfo {{ brand: {:?}, marketing_name: {:?}, form_factor: FormFactor::{}, chip: {:?}, scale: {:?}, viewport_width: {:?}, release_year: {} }}),",
+            row.code, row.brand, row.marketing_name, row.form_factor, row.chip, row.scale, row.viewport_width, row.release_year
+        ).unwrap();
+    }
+    writeln!(out, "];").unwrap();
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/phf.rs");
+
+    for &(path, out_file) in TABLES.iter() {
+        generate(path, out_file)
+    }
+}
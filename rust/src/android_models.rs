@@ -0,0 +1,79 @@
+use device::{FormFactor, ModelInfo};
+use phf;
+use tokenizer::tokenize;
+
+// `SEEDS` and `MODELS`, generated by `build.rs` from `data/android_models.tsv`
+include!(concat!(env!("OUT_DIR"), "/android_models.rs"));
+
+/// Look up a model code (eg. `SM-G991B`, `Pixel 5`), ignoring ASCII case
+pub fn lookup(code: &str) -> Option<&'static ModelInfo> {
+    let (key, ref info) = MODELS[phf::slot(code, &SEEDS, MODELS.len())];
+
+    if key.eq_ignore_ascii_case(code) { Some(info) } else { None }
+}
+
+/// Items that can follow the Android version without being the model
+fn is_model(item: &str) -> bool {
+    let bytes  = item.as_bytes();
+    let locale = bytes.len() == 5 && (bytes[2] == b'-' || bytes[2] == b'_') &&
+        bytes.iter().enumerate().all(|(index, b)| index == 2 || b.is_ascii_alphabetic());
+
+    // `K` is the placeholder model of reduced user agents
+    item.len() > 2 && !locale && !item.contains(':') &&
+        !["Mobile", "Tablet", "Build"].iter().any(|token| item.starts_with(token))
+}
+
+/// The model code from `(Linux; Android 11; SM-G991B Build/RP1A...)`: the first item after the
+/// Android version that isn't a locale or flag, without its build ID. Samsung Internet prefixes
+/// the brand (`SAMSUNG SM-G991B`), which is dropped.
+pub fn model_code(ua: &str) -> Option<&str> {
+    for comment in tokenize(ua).comments() {
+        let mut items = comment.items().skip_while(|item| !item.text.starts_with("Android "));
+        if items.next().is_none() { continue }
+
+        let model = items
+            .map(|item| item.text.split(" Build/").next().unwrap_or(item.text).trim())
+            .find(|&item| is_model(item))?;
+
+        return Some(model.trim_start_matches("SAMSUNG ").trim_start_matches("samsung "))
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use device::FormFactor;
+    use super::{lookup, model_code, MODELS};
+
+    #[test]
+    fn test_every_model_is_found() {
+        for &(code, ref info) in MODELS.iter() {
+            assert_eq!(lookup(code), Some(info));
+            assert_eq!(lookup(&code.to_lowercase()), Some(info));
+        }
+
+        assert_eq!(lookup("SM-G991Z"), None);
+        assert_eq!(lookup(""), None);
+    }
+
+    #[test]
+    fn test_lookup() {
+        let s21 = lookup("SM-G991B").unwrap();
+
+        assert_eq!(s21.full_name(), "Samsung Galaxy S21");
+        assert_eq!((s21.form_factor, s21.release_year), (FormFactor::Phone, 2021));
+        assert_eq!(lookup("SM-X700").unwrap().form_factor, FormFactor::Tablet);
+    }
+
+    #[test]
+    fn test_model_code() {
+        assert_eq!(model_code("Mozilla/5.0 (Linux; Android 11; SM-G991B) AppleWebKit/537.36"), Some("SM-G991B"));
+        assert_eq!(model_code("Mozilla/5.0 (Linux; U; Android 4.0.3; ko-kr; LG-L160L Build/IML74K) AppleWebkit/534.30"), Some("LG-L160L"));
+        assert_eq!(model_code("Mozilla/5.0 (Linux; Android 4.4.4; One Build/KTU84L.H4) AppleWebKit/537.36"), Some("One"));
+        assert_eq!(model_code("Mozilla/5.0 (Linux; Android 9; Redmi Note 8 Pro) AppleWebKit/537.36"), Some("Redmi Note 8 Pro"));
+        assert_eq!(model_code("Mozilla/5.0 (Linux; Android 11; SAMSUNG SM-G991B) AppleWebKit/537.36"), Some("SM-G991B"));
+        assert_eq!(model_code("Mozilla/5.0 (Linux; Android 10; K) AppleWebKit/537.36"), None);
+        assert_eq!(model_code("Mozilla/5.0 (Android 4.4; Mobile; rv:41.0) Gecko/41.0 Firefox/41.0"), None);
+    }
+}
@@ -0,0 +1,279 @@
+use regex::Regex;
+use std::fmt;
+use std::io;
+use std::str::FromStr;
+
+use platform::{Platform, PlatformName};
+
+/// A native app, from the product token at the start of its user agent
+/// (eg. `MyApp/3.2.1 CFNetwork/1220.1 Darwin/20.3.0`).
+#[derive(Clone, Debug, PartialEq)]
+pub struct App {
+    /// `None` when the user agent is the runtime's default (`Dalvik/2.1.0 (Linux; ...)`) rather
+    /// than one the app set, since that doesn't name the app
+    pub name: Option<String>,
+    /// Apps version themselves however they like, so this is kept as sent
+    pub version: Option<String>,
+    /// Device model as the app reports it (eg. `iPhone`, `Pixel 5`)
+    pub model: Option<String>,
+    /// Screen scale factor (`Scale/3.00`)
+    pub scale: Option<f32>,
+    /// The OS the app reports, if its format includes one
+    pub os: Option<PlatformName>,
+    pub os_major_version: i16,
+    pub os_minor_version: i16,
+}
+
+impl fmt::Display for App {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = self.name.as_deref().unwrap_or("Unknown app");
+
+        match self.version {
+            Some(ref version) => write!(f, "{} {}", name, version),
+            None              => f.write_str(name),
+        }
+    }
+}
+
+/// Decode `%XX` escapes; CFNetwork percent-encodes the bundle name (`My%20App/1.0`).
+fn percent_decode(input: &str) -> String {
+    let bytes       = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index   = 0;
+
+    while index < bytes.len() {
+        let escape = if bytes[index] == b'%' && index + 2 < bytes.len() {
+            input.get(index + 1..index + 3).and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        } else {
+            None
+        };
+
+        match escape {
+            Some(byte) => { decoded.push(byte); index += 3 },
+            None       => { decoded.push(bytes[index]); index += 1 },
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Runtimes whose default user agent looks like an app's
+const RUNTIMES: [&str; 1] = ["Dalvik"];
+
+/// A user agent format used by native apps. The regex must have a `name` capture group and may
+/// have `version`, `model`, `scale` and `os_version` ones; `os_version` is only used when the
+/// format has an `os`.
+#[derive(Clone, Debug)]
+pub struct AppFormat {
+    pub id: String,
+    pub os: Option<PlatformName>,
+    regex: Regex,
+}
+
+impl AppFormat {
+    pub fn new(id: &str, pattern: &str, os: Option<PlatformName>) -> io::Result<AppFormat> {
+        let invalid_input = |message: String| io::Error::new(io::ErrorKind::InvalidInput, message);
+
+        let regex = Regex::new(pattern).map_err(|error| invalid_input(error.to_string()))?;
+        if !regex.capture_names().any(|name| name == Some("name")) {
+            return Err(invalid_input(format!("{}: missing a `name` capture group", id)))
+        }
+
+        Ok(AppFormat {
+            id:    id.to_owned(),
+            os:    os,
+            regex: regex,
+        })
+    }
+
+    pub fn parse(&self, ua: &str) -> Option<App> {
+        let captures = self.regex.captures(ua)?;
+
+        let name = percent_decode(captures.name("name")?);
+        // Browsers' `Mozilla/5.0 (Linux; Android 11; ...)` looks just like an app's user agent
+        if name.is_empty() || name == "Mozilla" { return None }
+
+        let owned   = |group| captures.name(group).filter(|v| !v.is_empty()).map(str::to_owned);
+        let runtime = RUNTIMES.contains(&name.as_str());
+
+        let mut os_version = captures.name("os_version").unwrap_or("").split(['.', '_']);
+        let mut version    = || os_version.next().and_then(|v| i16::from_str(v).ok()).unwrap_or(0);
+        let os_major       = version();
+        let os_minor       = version();
+
+        Some(App {
+            name:             if runtime { None } else { Some(name) },
+            version:          if runtime { None } else { owned("version") },
+            model:            owned("model"),
+            scale:            captures.name("scale").and_then(|s| f32::from_str(s).ok()),
+            os:               self.os.clone(),
+            os_major_version: os_major,
+            os_minor_version: os_minor,
+        })
+    }
+}
+
+const DEFAULT_FORMATS: &[(&str, &str, Option<PlatformName>)] = &[
+    // `MyApp/3.2.1 CFNetwork/1220.1 Darwin/20.3.0`; `Platform` handles the CFNetwork and Darwin
+    // versions, since they're there whichever format the app uses
+    ("cfnetwork",     r"^(?P<name>[^/\s]+)(?:/(?P<version>\S+))? CFNetwork/", None),
+    // `MyApp/1.2.3 (iPhone; iOS 14.2; Scale/3.00)`, the default in AFNetworking and our SDKs
+    ("ios-sdk",       r"^(?P<name>[^/\s]+)/(?P<version>\S+) \((?P<model>iPhone|iPad|iPod)[^;)]*; iOS (?P<os_version>[\d.]+)(?:; Scale/(?P<scale>[\d.]+))?\)", Some(PlatformName::IOS)),
+    // `MyApp/1.2.3 (com.example.MyApp; build:42; iOS 14.2.0) Alamofire/5.4.0`
+    ("alamofire",     r"^(?P<name>[^/\s]+)/(?P<version>\S+) \([^;)]+; build:[^;)]+; iOS (?P<os_version>[\d.]+)\) Alamofire/", Some(PlatformName::IOS)),
+    // `MyApp/1.2.3 Dalvik/2.1.0 (Linux; U; Android 11; Pixel 5 Build/RQ3A)`
+    ("android-app",   r"^(?P<name>[^/\s]+)/(?P<version>\S+) Dalvik/[\d.]+ \(Linux; U; Android (?P<os_version>[\d.]+); (?P<model>[^;)]+?)(?: Build/[^;)]*)?\)", Some(PlatformName::Android)),
+    // `Dalvik/2.1.0 (Linux; U; Android 11; Pixel 5 Build/RQ3A)`, Android's default, and
+    // `MyApp/1.2.3 (Android 11; Pixel 5) okhttp/4.9.0`
+    ("android-sdk",   r"^(?P<name>[^/\s]+)/(?P<version>[^\s(]+) \((?:Linux; U; )?Android (?P<os_version>[\d.]+); (?P<model>[^;)]+?)(?: Build/[^;)]*)?(?:;[^)]*)?\)", Some(PlatformName::Android)),
+];
+
+lazy_static! {
+    static ref DEFAULT_REGISTRY: AppRegistry = AppRegistry::default();
+}
+
+/// The native app formats to try, in order. Teams can add the format their own app sends.
+#[derive(Clone, Debug)]
+pub struct AppRegistry {
+    formats: Vec<AppFormat>,
+}
+
+impl Default for AppRegistry {
+    fn default() -> AppRegistry {
+        let formats = DEFAULT_FORMATS.iter()
+            .map(|&(id, pattern, ref os)| AppFormat::new(id, pattern, os.clone()).unwrap())
+            .collect();
+
+        AppRegistry::new(formats)
+    }
+}
+
+impl AppRegistry {
+    pub fn new(formats: Vec<AppFormat>) -> AppRegistry {
+        AppRegistry { formats: formats }
+    }
+
+    /// Registered formats are tried before the built-in ones
+    pub fn add_format(&mut self, format: AppFormat) {
+        self.formats.insert(0, format)
+    }
+
+    pub fn parse(&self, ua: &str) -> Option<App> {
+        self.formats.iter().filter_map(|format| format.parse(ua)).next()
+    }
+}
+
+impl App {
+    /// Parse with the built-in formats; see `AppRegistry` to add more
+    pub fn parse(ua: &str) -> Option<App> {
+        DEFAULT_REGISTRY.parse(ua)
+    }
+
+    /// The OS the app reported, for user agents without a browser's platform tokens
+    pub fn platform(&self) -> Option<Platform> {
+        let os = self.os.clone()?;
+
+        Some(Platform::new(os, self.os_major_version, self.os_minor_version))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use device::FormFactor;
+    use platform::PlatformName;
+    use user_agent::UserAgent;
+    use super::{App, AppFormat, AppRegistry};
+
+    type StaticStr = &'static str;
+
+    const IOS_SDK: StaticStr   = "AppName/1.2.3 (iPhone; iOS 14.2; Scale/3.00)";
+    const DALVIK: StaticStr    = "Dalvik/2.1.0 (Linux; U; Android 11; Pixel 5 Build/RQ3A.210805.001.A1)";
+    const ALAMOFIRE: StaticStr = "MyApp/2.0 (com.example.MyApp; build:42; iOS 14.2.0) Alamofire/5.4.0";
+    const CHROME: StaticStr    = "Mozilla/5.0 (Linux; Android 13; SM-X700) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+    const OPERA: StaticStr     = "Opera/9.80 (Android 2.3.3; Linux; Opera Mobi/ADR-1111101157; U; es-ES) Presto/2.9.201 Version/11.50";
+
+    fn app(ua: &str) -> Option<(String, Option<String>)> {
+        App::parse(ua).map(|app| (app.name.unwrap(), app.version))
+    }
+
+    #[test]
+    fn test_parse_cfnetwork_app() {
+        assert_eq!(
+            app("MyApp/3.2.1 CFNetwork/1220.1 Darwin/20.3.0"),
+            Some(("MyApp".to_owned(), Some("3.2.1".to_owned())))
+        );
+        assert_eq!(
+            app("My%20App/45 CFNetwork/1494.0.7 Darwin/23.4.0"),
+            Some(("My App".to_owned(), Some("45".to_owned())))
+        );
+    }
+
+    #[test]
+    fn test_parse_without_app() {
+        assert_eq!(app("CFNetwork/1220.1 Darwin/20.3.0"), None);
+        assert_eq!(app("okhttp/4.9.0"), None);
+        assert_eq!(app(CHROME), None);
+        assert_eq!(App::parse("Podcasts CFNetwork/1220.1 Darwin/20.3.0").unwrap().version, None);
+    }
+
+    #[test]
+    fn test_parse_ios_sdk() {
+        let app = App::parse(IOS_SDK).unwrap();
+
+        assert_eq!((app.name.as_deref(), app.version.as_deref()), (Some("AppName"), Some("1.2.3")));
+        assert_eq!((app.model.as_deref(), app.scale), (Some("iPhone"), Some(3.0)));
+        assert_eq!((app.os, app.os_major_version, app.os_minor_version), (Some(PlatformName::IOS), 14, 2));
+
+        assert_eq!(App::parse(ALAMOFIRE).unwrap().os_major_version, 14);
+    }
+
+    #[test]
+    fn test_parse_dalvik() {
+        // Android's default user agent names the runtime, not the app
+        let app = App::parse(DALVIK).unwrap();
+
+        assert_eq!((app.name, app.version), (None, None));
+        assert_eq!(app.model.as_deref(), Some("Pixel 5"));
+        assert_eq!((app.os, app.os_major_version), (Some(PlatformName::Android), 11));
+
+        let prefixed = App::parse(&format!("MyApp/4.1 {}", DALVIK)).unwrap();
+        assert_eq!((prefixed.name.as_deref(), prefixed.model.as_deref()), (Some("MyApp"), Some("Pixel 5")));
+        assert_eq!(prefixed.os_major_version, 11);
+    }
+
+    #[test]
+    fn test_user_agent_falls_back_to_app_platform() {
+        let ua = UserAgent::parse(IOS_SDK);
+        assert!(ua.bot.is_none());
+
+        let platform = ua.platform.unwrap();
+        assert_eq!((platform.name, platform.major_version, platform.minor_version), (PlatformName::IOS, 14, 2));
+        assert!(UserAgent::parse(DALVIK).bot.is_none());
+    }
+
+    #[test]
+    fn test_browsers_are_not_apps() {
+        let opera = UserAgent::parse(OPERA);
+
+        assert_eq!(App::parse(OPERA).and_then(|app| app.name), Some("Opera".to_owned()));
+        assert!(opera.app.is_none());
+        assert_eq!(opera.device.unwrap().form_factor, FormFactor::Phone);
+    }
+
+    #[test]
+    fn test_custom_format() {
+        let mut registry = AppRegistry::default();
+        registry.add_format(AppFormat::new(
+            "acme", r"^Acme (?P<name>\w+) v(?P<version>[\d.]+) on (?P<model>[^/]+)/Android (?P<os_version>[\d.]+)$",
+            Some(PlatformName::Android),
+        ).unwrap());
+
+        let ua  = UserAgent::parse_with_apps("Acme Rockets v2.1 on Pixel 7/Android 14", &registry);
+        let app = ua.app.unwrap();
+        assert_eq!((app.name.as_deref(), app.model.as_deref()), (Some("Rockets"), Some("Pixel 7")));
+        assert_eq!(ua.platform.unwrap().major_version, 14);
+
+        assert!(AppFormat::new("broken", r"^(\w+)/", None).is_err());
+        assert!(AppFormat::new("broken", r"^(?P<name>\w+", None).is_err());
+    }
+}
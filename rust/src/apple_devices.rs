@@ -0,0 +1,62 @@
+use regex::Regex;
+
+use device::{FormFactor, ModelInfo};
+use phf;
+
+// `SEEDS` and `MODELS`, generated by `build.rs` from `data/apple_devices.tsv`
+include!(concat!(env!("OUT_DIR"), "/apple_devices.rs"));
+
+lazy_static! {
+    static ref IDENTIFIER_REGEX: Regex = Regex::new(r"\b(?:iPhone|iPad|iPod|Watch|AppleTV)\d+,\d+\b").unwrap();
+}
+
+/// Look up a hardware identifier (eg. `iPhone12,1`), ignoring ASCII case
+pub fn lookup(identifier: &str) -> Option<&'static ModelInfo> {
+    let (key, ref info) = MODELS[phf::slot(identifier, &SEEDS, MODELS.len())];
+
+    if key.eq_ignore_ascii_case(identifier) { Some(info) } else { None }
+}
+
+/// The first hardware identifier in the user agent. Browsers never send one, but native apps
+/// often report the device this way (`MyApp/2.0 (iPhone12,1; iOS 14.2; Scale/2.00)`).
+pub fn identifier(ua: &str) -> Option<&str> {
+    IDENTIFIER_REGEX.find(ua).map(|(start, end)| &ua[start..end])
+}
+
+/// Whether the identifier is for a device that runs iOS or iPadOS
+pub fn is_ios_device(identifier: &str) -> bool {
+    ["iPhone", "iPad", "iPod"].iter().any(|prefix| identifier.starts_with(prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use device::FormFactor;
+    use super::{identifier, lookup, MODELS};
+
+    #[test]
+    fn test_every_model_is_found() {
+        for &(code, ref info) in MODELS.iter() {
+            assert_eq!(lookup(code), Some(info));
+        }
+
+        assert_eq!(lookup("iPhone99,1"), None);
+    }
+
+    #[test]
+    fn test_lookup() {
+        let iphone = lookup("iPhone12,1").unwrap();
+        assert_eq!((iphone.marketing_name, iphone.chip, iphone.release_year), ("iPhone 11", Some("A13 Bionic"), 2019));
+
+        let ipad = lookup("iPad8,6").unwrap();
+        assert_eq!((ipad.marketing_name, ipad.form_factor), ("iPad Pro 12.9-inch (3rd gen)", FormFactor::Tablet));
+
+        assert_eq!(lookup("Watch6,2").unwrap().form_factor, FormFactor::Wearable);
+    }
+
+    #[test]
+    fn test_identifier() {
+        assert_eq!(identifier("MyApp/2.0 (iPhone12,1; iOS 14.2; Scale/2.00)"), Some("iPhone12,1"));
+        assert_eq!(identifier("MyApp/2.0 (Watch6,2; watchOS 7.0)"), Some("Watch6,2"));
+        assert_eq!(identifier("Mozilla/5.0 (iPhone; CPU iPhone OS 8_0_2 like Mac OS X)"), None);
+    }
+}
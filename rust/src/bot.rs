@@ -1,34 +1,253 @@
+use heuristic::{Confidence, HeuristicMatch};
 use regex::{Regex};
+use std::fmt;
+use user_agent::UserAgent;
 use util::map_first_captures;
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum BotName {
+    Amazonbot,
+    AnthropicAI,
+    ApplebotExtended,
     Baidu,
     Bingbot,
+    Bytespider,
+    CCBot,
+    ChatGPTUser,
+    ClaudeBot,
+    ClaudeSearchBot,
+    ClaudeUser,
+    CohereAI,
+    DatadogSynthetics,
+    Diffbot,
+    Discordbot,
     DuckDuckBot,
-    Go, // Go language's HTTP package
+    FacebookExternalHit,
+    GPTBot,
+    GoogleExtended,
     Googlebot,
+    IMessagePreview, // Impersonates facebookexternalhit and Twitterbot
+    Lighthouse, // Also used by PageSpeed Insights
+    LinkedInBot,
+    Masscan,
+    MetaExternalAgent,
+    MetaExternalFetcher,
+    NewRelicSynthetics,
+    Nikto,
+    NmapScriptingEngine,
+    Nuclei,
+    OAISearchBot,
+    PerplexityBot,
+    PerplexityUser,
+    Pingdom,
+    Pinterestbot,
+    ScreamingFrog,
+    Site24x7,
+    SkypeUriPreview,
+    SlackbotLinkExpanding,
+    Sqlmap,
+    StatusCake,
+    TelegramBot,
+    Timpibot,
+    Twitterbot,
+    Unknown, // Not in the catalog, but flagged by `HeuristicMatch`
+    UptimeRobot,
+    WPScan,
+    WhatsApp,
+    YouBot,
+    Zgrab,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BotCategory {
+    AiCrawler,
+    /// Uptime and synthetic transaction monitoring
+    Monitor,
+    Other,
+    Search,
+    /// Vulnerability scanners and exploitation tools
+    SecurityScanner,
+    /// SEO crawlers and page-quality auditors
+    SeoAuditor,
+    /// Fetches pages to render link previews (OpenGraph/Twitter cards) in social networks and
+    /// messaging apps
+    SocialPreview,
 }
 
-impl ToString for BotName {
-    fn to_string(&self) -> String {
+impl fmt::Display for BotCategory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+impl BotName {
+    pub fn category(&self) -> BotCategory {
         use self::BotName::*;
 
+        if self.ai_crawler_purpose().is_some() {
+            return BotCategory::AiCrawler
+        }
+
         match *self {
-            Go => "Go HTTP package".to_owned(),
-            _ => format!("{:?}", self)
+            Baidu       => BotCategory::Search,
+            Bingbot     => BotCategory::Search,
+            DuckDuckBot => BotCategory::Search,
+            Googlebot   => BotCategory::Search,
+
+            Discordbot            => BotCategory::SocialPreview,
+            FacebookExternalHit   => BotCategory::SocialPreview,
+            IMessagePreview       => BotCategory::SocialPreview,
+            LinkedInBot           => BotCategory::SocialPreview,
+            Pinterestbot          => BotCategory::SocialPreview,
+            SkypeUriPreview       => BotCategory::SocialPreview,
+            SlackbotLinkExpanding => BotCategory::SocialPreview,
+            TelegramBot           => BotCategory::SocialPreview,
+            Twitterbot            => BotCategory::SocialPreview,
+            WhatsApp              => BotCategory::SocialPreview,
+
+            DatadogSynthetics  => BotCategory::Monitor,
+            NewRelicSynthetics => BotCategory::Monitor,
+            Pingdom            => BotCategory::Monitor,
+            Site24x7           => BotCategory::Monitor,
+            StatusCake         => BotCategory::Monitor,
+            UptimeRobot        => BotCategory::Monitor,
+
+            Lighthouse    => BotCategory::SeoAuditor,
+            ScreamingFrog => BotCategory::SeoAuditor,
+
+            Masscan             => BotCategory::SecurityScanner,
+            Nikto               => BotCategory::SecurityScanner,
+            NmapScriptingEngine => BotCategory::SecurityScanner,
+            Nuclei              => BotCategory::SecurityScanner,
+            Sqlmap              => BotCategory::SecurityScanner,
+            WPScan              => BotCategory::SecurityScanner,
+            Zgrab               => BotCategory::SecurityScanner,
+
+            _ => BotCategory::Other,
         }
     }
+
+    /// What an AI/LLM crawler fetches pages for, or `None` if this isn't an AI crawler.
+    pub fn ai_crawler_purpose(&self) -> Option<AiCrawlerPurpose> {
+        use self::AiCrawlerPurpose::*;
+        use self::BotName::*;
+
+        match *self {
+            AnthropicAI       => Some(Training),
+            ApplebotExtended  => Some(Training),
+            Bytespider        => Some(Training),
+            CCBot             => Some(Training),
+            ClaudeBot         => Some(Training),
+            Diffbot           => Some(Training),
+            GPTBot            => Some(Training),
+            GoogleExtended    => Some(Training),
+            MetaExternalAgent => Some(Training),
+            Timpibot          => Some(Training),
+
+            Amazonbot       => Some(Search),
+            ClaudeSearchBot => Some(Search),
+            OAISearchBot    => Some(Search),
+            PerplexityBot   => Some(Search),
+            YouBot          => Some(Search),
+
+            ChatGPTUser         => Some(UserFetch),
+            ClaudeUser          => Some(UserFetch),
+            CohereAI            => Some(UserFetch),
+            MetaExternalFetcher => Some(UserFetch),
+            PerplexityUser      => Some(UserFetch),
+
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for BotName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::BotName::*;
+
+        let name = match *self {
+            AnthropicAI           => "anthropic-ai",
+            ApplebotExtended      => "Applebot-Extended",
+            ChatGPTUser           => "ChatGPT-User",
+            ClaudeSearchBot       => "Claude-SearchBot",
+            ClaudeUser            => "Claude-User",
+            CohereAI              => "cohere-ai",
+            FacebookExternalHit   => "facebookexternalhit",
+            GoogleExtended        => "Google-Extended",
+            IMessagePreview       => "iMessage link preview",
+            MetaExternalAgent     => "meta-externalagent",
+            MetaExternalFetcher   => "meta-externalfetcher",
+            NmapScriptingEngine   => "Nmap Scripting Engine",
+            OAISearchBot          => "OAI-SearchBot",
+            PerplexityUser        => "Perplexity-User",
+            ScreamingFrog         => "Screaming Frog SEO Spider",
+            SlackbotLinkExpanding => "Slackbot-LinkExpanding",
+            _ => return fmt::Debug::fmt(self, f),
+        };
+
+        f.write_str(name)
+    }
+}
+
+/// Why an AI/LLM crawler is fetching a page. Operators generally publish separate user agents
+/// for each of these so that site owners can opt out of them independently.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AiCrawlerPurpose {
+    /// Collecting content to train models
+    Training,
+    /// Indexing content for AI search and retrieval-augmented answers
+    Search,
+    /// Fetching a page on behalf of a user's live request (eg. "summarize this link")
+    UserFetch,
+}
+
+/// How a `Bot` was identified.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BotSource {
+    /// Matched an entry in `MATCH_SEQUENCE`
+    Catalog,
+    /// Not in the catalog, but looked automated enough to be flagged
+    Heuristic(HeuristicMatch),
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct Bot {
     pub name: BotName,
+    pub source: BotSource,
 }
 
 impl Bot {
     pub fn new(name: BotName) -> Bot {
-        Bot { name: name, }
+        Bot {
+            name:   name,
+            source: BotSource::Catalog,
+        }
+    }
+
+    /// Fall back to `HeuristicMatch` for bots that aren't in the catalog. Only matches with at
+    /// least `Confidence::Medium` are treated as bots.
+    pub fn parse_heuristic(ua: &str) -> Option<Bot> {
+        HeuristicMatch::analyze(ua)
+            .and_then(|analyzed| {
+                if analyzed.confidence < Confidence::Medium { return None }
+
+                Some(Bot {
+                    name:   BotName::Unknown,
+                    source: BotSource::Heuristic(analyzed),
+                })
+            })
+    }
+
+    pub fn is_heuristic(&self) -> bool {
+        matches!(self.source, BotSource::Heuristic(_))
+    }
+
+    pub fn is_ai_crawler(&self) -> bool {
+        self.name.ai_crawler_purpose().is_some()
+    }
+
+    pub fn is_link_preview(&self) -> bool {
+        self.name.category() == BotCategory::SocialPreview
     }
 
     pub fn parse(ua: &str) -> Option<Bot> {
@@ -41,7 +260,7 @@ impl Bot {
         None
     }
 
-    fn make_matcher(search: &str, name: BotName) -> Box<Fn(&str) -> Option<Bot> + Sync> {
+    fn make_matcher(search: &str, name: BotName) -> Matcher {
         let search = search.to_owned();
 
         Box::new(move |ua: &str| {
@@ -53,8 +272,20 @@ impl Bot {
         })
     }
 
-    fn make_version_regex_matcher(regex: Regex, name: BotName)
-                                  -> Box<Fn(&str) -> Option<Bot> + Sync> {
+    /// Like `make_matcher`, but every one of the `searches` must be present.
+    fn make_all_matcher(searches: &[&str], name: BotName) -> Matcher {
+        let searches: Vec<String> = searches.iter().map(|s| (*s).to_owned()).collect();
+
+        Box::new(move |ua: &str| {
+            if searches.iter().all(|search| ua.contains(search)) {
+                Some(Bot::new(name.clone()))
+            } else {
+                None
+            }
+        })
+    }
+
+    fn make_version_regex_matcher(regex: Regex, name: BotName) -> Matcher {
         Box::new(move |ua: &str| {
             regex
                 .captures(ua)
@@ -78,18 +309,171 @@ lazy_static! {
             Bot::make_version_regex_matcher(bingbot_regex, BotName::Bingbot),
             Bot::make_matcher("Baidu", BotName::Baidu),
             Bot::make_matcher("DuckDuckBot", BotName::DuckDuckBot),
-            Bot::make_matcher("Go-http-client", BotName::Go),
+
+            // AI/LLM crawlers
+            Bot::make_matcher("GPTBot", BotName::GPTBot),
+            Bot::make_matcher("ChatGPT-User", BotName::ChatGPTUser),
+            Bot::make_matcher("OAI-SearchBot", BotName::OAISearchBot),
+            Bot::make_matcher("ClaudeBot", BotName::ClaudeBot),
+            Bot::make_matcher("Claude-User", BotName::ClaudeUser),
+            Bot::make_matcher("Claude-SearchBot", BotName::ClaudeSearchBot),
+            Bot::make_matcher("anthropic-ai", BotName::AnthropicAI),
+            Bot::make_matcher("CCBot", BotName::CCBot),
+            Bot::make_matcher("PerplexityBot", BotName::PerplexityBot),
+            Bot::make_matcher("Perplexity-User", BotName::PerplexityUser),
+            Bot::make_matcher("Bytespider", BotName::Bytespider),
+            Bot::make_matcher("meta-externalagent", BotName::MetaExternalAgent),
+            Bot::make_matcher("meta-externalfetcher", BotName::MetaExternalFetcher),
+            Bot::make_matcher("Amazonbot", BotName::Amazonbot),
+            Bot::make_matcher("cohere-ai", BotName::CohereAI),
+            Bot::make_matcher("Diffbot", BotName::Diffbot),
+            Bot::make_matcher("YouBot", BotName::YouBot),
+            Bot::make_matcher("Timpibot", BotName::Timpibot),
+            // Google-Extended and Applebot-Extended are documented as robots.txt product tokens
+            // and normally crawl as Googlebot/Applebot, but match them in case they're sent.
+            Bot::make_matcher("Google-Extended", BotName::GoogleExtended),
+            Bot::make_matcher("Applebot-Extended", BotName::ApplebotExtended),
+
+            // Social-media and messaging link previews. iMessage's fetcher sends both the
+            // Facebook and Twitter tokens, so it has to be checked before either of them.
+            Bot::make_all_matcher(&["facebookexternalhit", "Twitterbot"], BotName::IMessagePreview),
+            Bot::make_matcher("facebookexternalhit", BotName::FacebookExternalHit),
+            Bot::make_matcher("TelegramBot", BotName::TelegramBot),
+            Bot::make_matcher("Twitterbot", BotName::Twitterbot),
+            Bot::make_matcher("LinkedInBot", BotName::LinkedInBot),
+            Bot::make_matcher("Slackbot-LinkExpanding", BotName::SlackbotLinkExpanding),
+            Bot::make_matcher("Discordbot", BotName::Discordbot),
+            Bot::make_matcher("WhatsApp/", BotName::WhatsApp),
+            Bot::make_matcher("Pinterestbot", BotName::Pinterestbot),
+            Bot::make_matcher("pinterest.com/bot", BotName::Pinterestbot),
+            Bot::make_matcher("SkypeUriPreview", BotName::SkypeUriPreview),
+
+            // Uptime monitors
+            Bot::make_matcher("Pingdom", BotName::Pingdom),
+            Bot::make_matcher("UptimeRobot", BotName::UptimeRobot),
+            Bot::make_matcher("StatusCake", BotName::StatusCake),
+            Bot::make_matcher("Datadog/Synthetics", BotName::DatadogSynthetics),
+            Bot::make_matcher("DatadogSynthetics", BotName::DatadogSynthetics),
+            Bot::make_matcher("NewRelicPinger", BotName::NewRelicSynthetics),
+            Bot::make_matcher("NewRelicSynthetics", BotName::NewRelicSynthetics),
+            Bot::make_matcher("Site24x7", BotName::Site24x7),
+
+            // SEO auditors
+            Bot::make_matcher("Chrome-Lighthouse", BotName::Lighthouse),
+            Bot::make_matcher("Google Page Speed Insights", BotName::Lighthouse),
+            Bot::make_matcher("Screaming Frog SEO Spider", BotName::ScreamingFrog),
+
+            // Security scanners
+            Bot::make_matcher("Nikto", BotName::Nikto),
+            Bot::make_matcher("sqlmap", BotName::Sqlmap),
+            Bot::make_matcher("Nuclei", BotName::Nuclei),
+            Bot::make_matcher("Nmap Scripting Engine", BotName::NmapScriptingEngine),
+            Bot::make_matcher("zgrab", BotName::Zgrab),
+            Bot::make_matcher("masscan", BotName::Masscan),
+            Bot::make_matcher("WPScan", BotName::WPScan),
         ]
     };
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PolicyDecision {
+    Allow,
+    Deny,
+}
+
+/// Allow/deny configuration for AI crawlers. Decisions are made per `AiCrawlerPurpose`, and can
+/// be overridden for individual bots. Everything is allowed by default.
+///
+/// ```ignore
+/// let policy = AiCrawlerPolicy::default()
+///     .with_purpose(AiCrawlerPurpose::Training, PolicyDecision::Deny)
+///     .with_bot(BotName::CCBot, PolicyDecision::Allow);
+///
+/// policy.decide(&UserAgent::parse(ua))
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct AiCrawlerPolicy {
+    training: PolicyDecision,
+    search: PolicyDecision,
+    user_fetch: PolicyDecision,
+    overrides: Vec<(BotName, PolicyDecision)>,
+}
+
+impl Default for AiCrawlerPolicy {
+    fn default() -> AiCrawlerPolicy {
+        AiCrawlerPolicy {
+            training:   PolicyDecision::Allow,
+            search:     PolicyDecision::Allow,
+            user_fetch: PolicyDecision::Allow,
+            overrides:  vec![],
+        }
+    }
+}
+
+impl AiCrawlerPolicy {
+    pub fn with_purpose(mut self, purpose: AiCrawlerPurpose, decision: PolicyDecision)
+                        -> AiCrawlerPolicy {
+        match purpose {
+            AiCrawlerPurpose::Training  => self.training = decision,
+            AiCrawlerPurpose::Search    => self.search = decision,
+            AiCrawlerPurpose::UserFetch => self.user_fetch = decision,
+        }
+        self
+    }
+
+    /// Override the purpose-level decision for a single bot. The most recent override for a
+    /// given bot wins.
+    pub fn with_bot(mut self, name: BotName, decision: PolicyDecision) -> AiCrawlerPolicy {
+        self.overrides.retain(|(n, _)| *n != name);
+        self.overrides.push((name, decision));
+        self
+    }
+
+    /// Decide whether the given user agent should be let through. Anything that isn't a
+    /// recognized AI crawler (including browsers and other bots) is always allowed.
+    pub fn decide(&self, ua: &UserAgent) -> PolicyDecision {
+        let name = match ua.bot {
+            Some(ref bot) => &bot.name,
+            None => return PolicyDecision::Allow,
+        };
+
+        if let Some(&(_, decision)) = self.overrides.iter().find(|(n, _)| n == name) {
+            return decision
+        }
+
+        match name.ai_crawler_purpose() {
+            Some(AiCrawlerPurpose::Training)  => self.training,
+            Some(AiCrawlerPurpose::Search)    => self.search,
+            Some(AiCrawlerPurpose::UserFetch) => self.user_fetch,
+            None => PolicyDecision::Allow,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{Bot, BotName};
+    use super::{AiCrawlerPolicy, AiCrawlerPurpose, Bot, BotCategory, BotName, PolicyDecision};
+    use heuristic::Confidence;
+    use user_agent::UserAgent;
 
-    const BAIDU: &'static str     = "Mozilla/5.0 (compatible; Baiduspider/2.0; +http://www.baidu.com/search/spider.html)";
-    const BINGBOT: &'static str   = "Mozilla/5.0 (iPhone; CPU iPhone OS 7_0 like Mac OS X) AppleWebKit/537.51.1 (KHTML, like Gecko) Version/7.0 Mobile/11A465 Safari/9537.53 (compatible; bingbot/2.0; +http://www.bing.com/bingbot.htm)";
-    const GOOGLEBOT: &'static str = "Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)";
+    type StaticStr = &'static str;
+
+    const BAIDU: StaticStr        = "Mozilla/5.0 (compatible; Baiduspider/2.0; +http://www.baidu.com/search/spider.html)";
+    const BINGBOT: StaticStr      = "Mozilla/5.0 (iPhone; CPU iPhone OS 7_0 like Mac OS X) AppleWebKit/537.51.1 (KHTML, like Gecko) Version/7.0 Mobile/11A465 Safari/9537.53 (compatible; bingbot/2.0; +http://www.bing.com/bingbot.htm)";
+    const GOOGLEBOT: StaticStr    = "Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)";
+    const CHATGPT_USER: StaticStr = "Mozilla/5.0 AppleWebKit/537.36 (KHTML, like Gecko); compatible; ChatGPT-User/1.0; +https://openai.com/bot";
+    const CLAUDEBOT: StaticStr    = "Mozilla/5.0 AppleWebKit/537.36 (KHTML, like Gecko; compatible; ClaudeBot/1.0; +claudebot@anthropic.com)";
+    const GPTBOT: StaticStr       = "Mozilla/5.0 AppleWebKit/537.36 (KHTML, like Gecko); compatible; GPTBot/1.1; +https://openai.com/gptbot";
+    const PERPLEXITY: StaticStr   = "Mozilla/5.0 AppleWebKit/537.36 (KHTML, like Gecko; compatible; PerplexityBot/1.0; +https://perplexity.ai/perplexitybot)";
+    const FACEBOOK: StaticStr     = "facebookexternalhit/1.1 (+http://www.facebook.com/externalhit_uatext.php)";
+    const IMESSAGE: StaticStr     = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_11_1) AppleWebKit/601.2.4 (KHTML, like Gecko) Version/9.0.1 Safari/601.2.4 facebookexternalhit/1.1 Facebot Twitterbot/1.0";
+    const SLACK: StaticStr        = "Slackbot-LinkExpanding 1.0 (+https://api.slack.com/robots)";
+    const TELEGRAM: StaticStr     = "TelegramBot (like TwitterBot)";
+    const LIGHTHOUSE: StaticStr   = "Mozilla/5.0 (Linux; Android 7.0; Moto G (4)) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/125.0.0.0 Mobile Safari/537.36 Chrome-Lighthouse";
+    const NMAP: StaticStr         = "Mozilla/5.0 (compatible; Nmap Scripting Engine; https://nmap.org/book/nse.html)";
+    const SQLMAP: StaticStr       = "sqlmap/1.4.7#stable (http://sqlmap.org)";
+    const UPTIMEROBOT: StaticStr  = "Mozilla/5.0+(compatible; UptimeRobot/2.0; http://www.uptimerobot.com/)";
+    const UNCATALOGUED: StaticStr = "Mozilla/5.0 (compatible; SomeNewBot/0.3; +https://example.com/bot)";
 
     #[test]
     fn test_parse_googlebot() {
@@ -114,4 +498,83 @@ mod tests {
             Bot::parse(BAIDU).unwrap()
         )
     }
+
+    #[test]
+    fn test_parse_ai_crawlers() {
+        assert_eq!(Bot::new(BotName::GPTBot), Bot::parse(GPTBOT).unwrap());
+        assert_eq!(Bot::new(BotName::ChatGPTUser), Bot::parse(CHATGPT_USER).unwrap());
+        assert_eq!(Bot::new(BotName::ClaudeBot), Bot::parse(CLAUDEBOT).unwrap());
+        assert_eq!(Bot::new(BotName::PerplexityBot), Bot::parse(PERPLEXITY).unwrap());
+    }
+
+    #[test]
+    fn test_ai_crawler_purpose() {
+        assert_eq!(BotName::GPTBot.ai_crawler_purpose(), Some(AiCrawlerPurpose::Training));
+        assert_eq!(BotName::PerplexityBot.ai_crawler_purpose(), Some(AiCrawlerPurpose::Search));
+        assert_eq!(BotName::ChatGPTUser.ai_crawler_purpose(), Some(AiCrawlerPurpose::UserFetch));
+        assert_eq!(BotName::Googlebot.ai_crawler_purpose(), None);
+        assert_eq!(BotName::ChatGPTUser.to_string(), "ChatGPT-User".to_owned());
+    }
+
+    #[test]
+    fn test_ai_crawler_policy() {
+        let policy = AiCrawlerPolicy::default()
+            .with_purpose(AiCrawlerPurpose::Training, PolicyDecision::Deny)
+            .with_bot(BotName::ClaudeBot, PolicyDecision::Allow);
+
+        assert_eq!(policy.decide(&UserAgent::parse(GPTBOT)), PolicyDecision::Deny);
+        assert_eq!(policy.decide(&UserAgent::parse(CLAUDEBOT)), PolicyDecision::Allow);
+        assert_eq!(policy.decide(&UserAgent::parse(CHATGPT_USER)), PolicyDecision::Allow);
+        assert_eq!(policy.decide(&UserAgent::parse(GOOGLEBOT)), PolicyDecision::Allow);
+    }
+
+    #[test]
+    fn test_parse_link_previews() {
+        assert_eq!(Bot::new(BotName::FacebookExternalHit), Bot::parse(FACEBOOK).unwrap());
+        assert_eq!(Bot::new(BotName::SlackbotLinkExpanding), Bot::parse(SLACK).unwrap());
+        assert_eq!(Bot::new(BotName::TelegramBot), Bot::parse(TELEGRAM).unwrap());
+
+        let slack = Bot::parse(SLACK).unwrap();
+        assert_eq!(slack.name.category(), BotCategory::SocialPreview);
+        assert!(slack.is_link_preview());
+        assert!(!Bot::parse(GOOGLEBOT).unwrap().is_link_preview());
+    }
+
+    #[test]
+    fn test_parse_imessage_impersonation() {
+        let imessage = Bot::parse(IMESSAGE).unwrap();
+
+        assert_eq!(imessage, Bot::new(BotName::IMessagePreview));
+        assert!(imessage.is_link_preview());
+    }
+
+    #[test]
+    fn test_parse_heuristic() {
+        assert_eq!(Bot::parse(UNCATALOGUED), None);
+
+        let bot = Bot::parse_heuristic(UNCATALOGUED).unwrap();
+        assert_eq!(bot.name, BotName::Unknown);
+        assert!(bot.is_heuristic());
+        assert!(!Bot::parse(GOOGLEBOT).unwrap().is_heuristic());
+
+        match bot.source {
+            super::BotSource::Heuristic(ref analyzed) => {
+                assert_eq!(analyzed.confidence, Confidence::High)
+            },
+            _ => panic!("expected a heuristic match"),
+        }
+    }
+
+    #[test]
+    fn test_parse_monitors_auditors_and_scanners() {
+        assert_eq!(Bot::new(BotName::UptimeRobot), Bot::parse(UPTIMEROBOT).unwrap());
+        assert_eq!(Bot::new(BotName::Lighthouse), Bot::parse(LIGHTHOUSE).unwrap());
+        assert_eq!(Bot::new(BotName::Sqlmap), Bot::parse(SQLMAP).unwrap());
+        assert_eq!(Bot::new(BotName::NmapScriptingEngine), Bot::parse(NMAP).unwrap());
+
+        assert_eq!(BotName::UptimeRobot.category(), BotCategory::Monitor);
+        assert_eq!(BotName::Lighthouse.category(), BotCategory::SeoAuditor);
+        assert_eq!(BotName::Sqlmap.category(), BotCategory::SecurityScanner);
+        assert_eq!(BotCategory::SecurityScanner.to_string(), "SecurityScanner".to_owned());
+    }
 }
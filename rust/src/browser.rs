@@ -1,5 +1,7 @@
 use regex::{Regex};
+use std::str::FromStr;
 
+use precision::Precision;
 use util::map_first_captures;
 
 #[derive(Clone, Debug, PartialEq)]
@@ -27,6 +29,27 @@ impl BrowserFamily {
     }
 }
 
+impl FromStr for BrowserFamily {
+    type Err = ();
+
+    /// Parses either the variant name (`"MobileSafari"`) or the display name (`"Mobile Safari"`)
+    fn from_str(string: &str) -> Result<BrowserFamily, ()> {
+        use self::BrowserFamily::*;
+
+        match string {
+            "Android"                        => Ok(Android),
+            "Chrome"                         => Ok(Chrome),
+            "Edge"                           => Ok(Edge),
+            "Firefox"                        => Ok(Firefox),
+            "Opera"                          => Ok(Opera),
+            "OperaMini" | "Opera Mini"       => Ok(OperaMini),
+            "Safari"                         => Ok(Safari),
+            "MobileSafari" | "Mobile Safari" => Ok(MobileSafari),
+            _ => Err(()),
+        }
+    }
+}
+
 impl ToString for BrowserFamily {
     fn to_string(&self) -> String {
         use self::BrowserFamily::*;
@@ -42,11 +65,12 @@ impl ToString for BrowserFamily {
 #[derive(Clone, Debug, PartialEq)]
 pub struct Browser {
     pub family: BrowserFamily,
-    pub major_version: i8,
-    pub minor_version: i8,
+    pub major_version: i16,
+    pub minor_version: i16,
+    pub precision: Precision,
 }
 
-type MatcherFn = Fn(&str) -> Option<(i8, i8)> + Sync;
+type MatcherFn = Fn(&str) -> Option<(i16, i16)> + Sync;
 type Matcher = (BrowserFamily, Box<MatcherFn>);
 
 lazy_static! {
@@ -64,11 +88,12 @@ lazy_static! {
 }
 
 impl Browser {
-    fn new(family: BrowserFamily, versions: (i8, i8)) -> Browser {
+    fn new(family: BrowserFamily, versions: (i16, i16)) -> Browser {
         Browser {
             family:        family,
             major_version: versions.0,
             minor_version: versions.1,
+            precision:     Precision::Exact,
         }
     }
 
@@ -78,22 +103,33 @@ impl Browser {
             let &(ref family, ref matcher) = tuple;
 
             if let Some(versions) = matcher(ua) {
-                let browser = Browser::new(family.clone(), versions);
+                let mut browser = Browser::new(family.clone(), versions);
+                browser.precision = Browser::match_precision(family, ua);
 
                 return Some(browser)
             }
         }
 
-        return None
+        None
     }
 
     /// Take a regex and attempt to match it to the browser. The regex must include two capture
     /// groups that capture the version of the matched browser.
-    fn match_versions(ua: &str, regex: &Regex) -> Option<(i8, i8)> {
+    fn match_versions(ua: &str, regex: &Regex) -> Option<(i16, i16)> {
         regex
             .captures(ua)
             .map(map_first_captures)
     }
+
+    /// Since Chrome 101 the minor, build and patch versions are frozen at `0.0.0`
+    fn match_precision(family: &BrowserFamily, ua: &str) -> Precision {
+        let frozen = match *family {
+            BrowserFamily::Chrome => CHROME_FROZEN_REGEX.is_match(ua),
+            _ => false,
+        };
+
+        if frozen { Precision::Frozen } else { Precision::Exact }
+    }
 }
 
 impl ToString for Browser {
@@ -110,46 +146,47 @@ lazy_static! {
     static ref OPERA_VERSION_REGEX: Regex   = Regex::new(r"Version/(\d+)\.(\d+)").unwrap();
     static ref SAFARI_VERSION_REGEX: Regex  = Regex::new(r"Version/(\d+)\.(\d+)").unwrap();
     static ref ANDROID_VERSION_REGEX: Regex = Regex::new(r"Version/(\d+)\.(\d+)").unwrap();
+    static ref CHROME_FROZEN_REGEX: Regex   = Regex::new(r"Chrom(?:ium|e)/\d+\.0\.0\.0\b").unwrap();
 }
 
 impl Browser {
-    pub fn match_android(ua: &str) -> Option<(i8, i8)> {
+    pub fn match_android(ua: &str) -> Option<(i16, i16)> {
         if !ua.contains("Android") { return None }
 
         Browser::match_versions(ua, &ANDROID_VERSION_REGEX)
     }
 
-    pub fn match_edge(ua: &str) -> Option<(i8, i8)> {
+    pub fn match_edge(ua: &str) -> Option<(i16, i16)> {
         Browser::match_versions(ua, &EDGE_REGEX)
     }
 
     /// Search for the Firefox componenet in the user agent and parse out the version if present
-    pub fn match_firefox(ua: &str) -> Option<(i8, i8)> {
+    pub fn match_firefox(ua: &str) -> Option<(i16, i16)> {
         Browser::match_versions(ua, &FIREFOX_REGEX)
     }
 
-    pub fn match_chrome(ua: &str) -> Option<(i8, i8)> {
+    pub fn match_chrome(ua: &str) -> Option<(i16, i16)> {
         Browser::match_versions(ua, &CHROME_REGEX)
     }
 
-    pub fn match_opera_mini(ua: &str) -> Option<(i8, i8)> {
+    pub fn match_opera_mini(ua: &str) -> Option<(i16, i16)> {
         Browser::match_versions(ua, &OPERA_MINI_REGEX)
     }
 
-    pub fn match_opera(ua: &str) -> Option<(i8, i8)> {
+    pub fn match_opera(ua: &str) -> Option<(i16, i16)> {
         if !ua.contains("Opera") { return None }
 
         Browser::match_versions(ua, &OPERA_VERSION_REGEX)
     }
 
-    pub fn match_safari(ua: &str) -> Option<(i8, i8)> {
+    pub fn match_safari(ua: &str) -> Option<(i16, i16)> {
         if !ua.contains("Safari") { return None }
         if ua.contains("Mobile/") { return None }
 
         Browser::match_versions(ua, &SAFARI_VERSION_REGEX)
     }
 
-    pub fn match_mobile_safari(ua: &str) -> Option<(i8, i8)> {
+    pub fn match_mobile_safari(ua: &str) -> Option<(i16, i16)> {
         if !ua.contains("Safari")  { return None }
         if !ua.contains("Mobile/") { return None }
 
@@ -159,6 +196,7 @@ impl Browser {
 
 #[cfg(test)]
 mod tests {
+    use precision::Precision;
     use super::{Browser, BrowserFamily};
 
     type StaticStr = &'static str;
@@ -169,6 +207,10 @@ mod tests {
     const OPERA_MINI_9: StaticStr    = "Opera/9.80 (J2ME/MIDP; Opera Mini/9.80 (S60; SymbOS; Opera Mobi/23.348; U; en) Presto/2.5.25 Version/10.54";
     const SAFARI_7: StaticStr        = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_9_3) AppleWebKit/537.75.14 (KHTML, like Gecko) Version/7.0.3 Safari/7046A194A";
     const SAFARI_5: StaticStr        = "Mozilla/5.0 (Macintosh; U; Intel Mac OS X 10_6_3; en-us) AppleWebKit/534.1+ (KHTML, like Gecko) Version/5.0 Safari/533.16";
+    const CHROME_120: StaticStr      = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+    const CHROME_120_FULL: StaticStr = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.6099.109 Safari/537.36";
+    const EDGE_120: StaticStr        = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36 Edg/120.0.2210.91";
+    const EDGE_12: StaticStr         = "Mozilla/5.0 (Windows NT 10.0) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/42.0.2311.135 Safari/537.36 Edge/12.10136";
     const MOBILE_SAFARI_6: StaticStr = "Mozilla/5.0 (iPad; CPU OS 6_0 like Mac OS X) AppleWebKit/536.26 (KHTML, like Gecko) Version/6.0 Mobile/10A5355d Safari/8536.25";
 
     #[test]
@@ -214,6 +256,33 @@ mod tests {
         assert_eq!(mobile_version_6, Some((6, 0)))
     }
 
+    #[test]
+    fn test_parse_chrome_3_digit_version() {
+        assert_eq!(
+            Browser { precision: Precision::Frozen, ..Browser::new(BrowserFamily::Chrome, (120, 0)) },
+            Browser::parse(CHROME_120).unwrap()
+        )
+    }
+
+    #[test]
+    fn test_precision() {
+        assert_eq!(Browser::parse(CHROME_120_FULL).unwrap().precision, Precision::Exact);
+        assert_eq!(Browser::parse(SAFARI_7).unwrap().precision, Precision::Exact);
+    }
+
+    #[test]
+    fn test_parse_edge() {
+        // Only EdgeHTML's `Edge/` token is matched; Chromium-based Edge is still parsed as Chrome
+        assert_eq!(Browser::parse(EDGE_12).unwrap().family, BrowserFamily::Edge);
+        assert_eq!(Browser::parse(EDGE_120).unwrap().family, BrowserFamily::Chrome);
+    }
+
+    #[test]
+    fn test_oversized_version_is_zero() {
+        let browser = Browser::parse("Mozilla/5.0 AppleWebKit/537.36 Chrome/99999.0 Safari/537.36").unwrap();
+        assert_eq!((browser.major_version, browser.minor_version), (0, 0));
+    }
+
     #[test]
     fn test_match_opera() {
         let opera_12 = Browser::match_opera(OPERA_12);
@@ -229,6 +298,13 @@ mod tests {
         assert_eq!(opera_mini_9, Some((9, 80)))
     }
 
+    #[test]
+    fn test_family_from_str() {
+        assert_eq!("Mobile Safari".parse(), Ok(BrowserFamily::MobileSafari));
+        assert_eq!("OperaMini".parse(), Ok(BrowserFamily::OperaMini));
+        assert_eq!("Netscape".parse::<BrowserFamily>(), Err(()));
+    }
+
     #[test]
     fn test_to_string() {
         let opera = Browser::new(BrowserFamily::Opera, (0, 0));
@@ -0,0 +1,245 @@
+use browser::BrowserFamily;
+use platform::PlatformName;
+use precision::Precision;
+use std::str::FromStr;
+use tokenizer::tokenize;
+use user_agent::UserAgent;
+
+/// An invariant that every genuine user agent satisfies. Rules are plain data so that callers
+/// can add their own to a `ConsistencyChecker`.
+#[derive(Clone, Copy, Debug)]
+pub struct Rule {
+    pub id: &'static str,
+    pub description: &'static str,
+    /// How strongly a violation suggests spoofing, from 0.0 to 1.0
+    pub weight: f32,
+    /// Returns true if the user agent breaks the rule
+    pub violated: fn(&UserAgent) -> bool,
+}
+
+pub const DEFAULT_RULES: &[Rule] = &[
+    Rule {
+        id:          "safari-on-windows",
+        description: "Safari hasn't been released for Windows since 5.1",
+        weight:      0.8,
+        violated:    safari_on_windows,
+    },
+    Rule {
+        id:          "modern-chrome-on-legacy-windows",
+        description: "Chrome 50 and later don't run on Windows XP or Vista",
+        weight:      0.7,
+        violated:    modern_chrome_on_legacy_windows,
+    },
+    Rule {
+        id:          "mobile-token-on-desktop",
+        description: "`Mobile/` build token on a desktop platform",
+        weight:      0.6,
+        violated:    mobile_token_on_desktop,
+    },
+    Rule {
+        id:          "iphone-with-android",
+        description: "Claims to be both an iPhone and Android",
+        weight:      0.9,
+        violated:    iphone_with_android,
+    },
+    Rule {
+        id:          "edge-chromium-mismatch",
+        description: "Edge's major version doesn't match its Chromium version",
+        weight:      0.7,
+        violated:    edge_chromium_mismatch,
+    },
+    Rule {
+        id:          "mobile-safari-ios-mismatch",
+        description: "Mobile Safari's major version doesn't match the iOS version",
+        weight:      0.4,
+        violated:    mobile_safari_ios_mismatch,
+    },
+];
+
+/// Find the `product/` token in the user agent and parse its major version.
+fn product_major_version(ua: &str, product: &str) -> Option<i16> {
+    let version = tokenize(ua).products().find(|p| p.name == product)?.version?;
+
+    i16::from_str(version.split('.').next()?).ok()
+}
+
+fn is_family(ua: &UserAgent, family: BrowserFamily) -> bool {
+    ua.browser.as_ref().is_some_and(|b| b.family == family)
+}
+
+fn is_platform(ua: &UserAgent, name: PlatformName) -> bool {
+    ua.platform.as_ref().is_some_and(|p| p.name == name)
+}
+
+fn safari_on_windows(ua: &UserAgent) -> bool {
+    is_family(ua, BrowserFamily::Safari) &&
+        is_platform(ua, PlatformName::Windows) &&
+        ua.browser.as_ref().is_some_and(|b| b.major_version >= 6)
+}
+
+fn modern_chrome_on_legacy_windows(ua: &UserAgent) -> bool {
+    // Windows versions are stored by marketing version, so Vista is 6.0 and 7 is 7.0. Zero
+    // means the version is unknown.
+    let legacy_windows = ua.platform.as_ref()
+        .is_some_and(|p| p.name == PlatformName::Windows && p.major_version > 0 && p.major_version < 7);
+
+    legacy_windows &&
+        is_family(ua, BrowserFamily::Chrome) &&
+        ua.browser.as_ref().is_some_and(|b| b.major_version >= 50)
+}
+
+fn mobile_token_on_desktop(ua: &UserAgent) -> bool {
+    let desktop = is_platform(ua, PlatformName::Windows) ||
+        is_platform(ua, PlatformName::Mac) ||
+        is_platform(ua, PlatformName::Linux);
+
+    desktop && ua.source.contains("Mobile/")
+}
+
+fn iphone_with_android(ua: &UserAgent) -> bool {
+    // Judged by the parsed platform: Windows Phone 8.1 names both (`Android 4.0; ...) like
+    // iPhone OS 7_0_3`), but parses as Windows Phone
+    let has_item = |matches: fn(&str) -> bool| {
+        tokenize(&ua.source).comments()
+            .flat_map(|comment| comment.items())
+            .any(|item| matches(item.text))
+    };
+
+    (is_platform(ua, PlatformName::IOS) && has_item(|item| item.starts_with("Android"))) ||
+        (is_platform(ua, PlatformName::Android) && has_item(|item| item == "iPhone"))
+}
+
+fn edge_chromium_mismatch(ua: &UserAgent) -> bool {
+    // Only Chromium-based Edge (`Edg/`) tracks the Chromium version; EdgeHTML (`Edge/`) didn't
+    match (product_major_version(&ua.source, "Edg"), product_major_version(&ua.source, "Chrome")) {
+        (Some(edge), Some(chrome)) => edge != chrome,
+        _ => false,
+    }
+}
+
+fn mobile_safari_ios_mismatch(ua: &UserAgent) -> bool {
+    let ios_major = match ua.platform {
+        Some(ref p) if p.name == PlatformName::IOS && p.major_version >= 8 && p.precision != Precision::Frozen => p.major_version,
+        _ => return false,
+    };
+
+    // Safari 26 freezes the OS at `iPhone OS 18_6`, so the versions no longer line up
+    match ua.browser {
+        Some(ref b) if b.family == BrowserFamily::MobileSafari && b.major_version < 26 => b.major_version != ios_major,
+        _ => false,
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ConsistencyReport {
+    pub violations: Vec<Rule>,
+    /// The chance that the user agent is spoofed, from 0.0 to 1.0. Each violation is treated as
+    /// independent evidence: `1 - (1 - w1) * (1 - w2) * ...`
+    pub spoof_likelihood: f32,
+}
+
+impl ConsistencyReport {
+    pub fn is_consistent(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+/// Checks a parsed user agent's browser, platform and engine tokens against each other.
+#[derive(Clone, Debug)]
+pub struct ConsistencyChecker {
+    rules: Vec<Rule>,
+}
+
+impl Default for ConsistencyChecker {
+    fn default() -> ConsistencyChecker {
+        ConsistencyChecker::new(DEFAULT_RULES.to_vec())
+    }
+}
+
+impl ConsistencyChecker {
+    pub fn new(rules: Vec<Rule>) -> ConsistencyChecker {
+        ConsistencyChecker { rules: rules }
+    }
+
+    pub fn add_rule(&mut self, rule: Rule) {
+        self.rules.push(rule)
+    }
+
+    pub fn check(&self, ua: &UserAgent) -> ConsistencyReport {
+        let violations: Vec<Rule> = self.rules.iter()
+            .filter(|rule| (rule.violated)(ua))
+            .cloned()
+            .collect();
+
+        let consistent_likelihood = violations.iter()
+            .fold(1.0, |likelihood, rule| likelihood * (1.0 - rule.weight.clamp(0.0, 1.0)));
+
+        ConsistencyReport {
+            violations:       violations,
+            spoof_likelihood: 1.0 - consistent_likelihood,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ConsistencyChecker, Rule};
+    use user_agent::UserAgent;
+
+    type StaticStr = &'static str;
+
+    const CHROME_41: StaticStr      = "Mozilla/5.0 (Windows NT 6.1) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/41.0.2228.0 Safari/537.36";
+    const CHROME_120_XP: StaticStr  = "Mozilla/5.0 (Windows NT 5.1) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+    const SAFARI_WINDOWS: StaticStr = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.0 Safari/605.1.15";
+    const EDGE_MISMATCH: StaticStr  = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/110.0.0.0 Safari/537.36 Edg/120.0.0.0";
+    const IPHONE_ANDROID: StaticStr = "Mozilla/5.0 (iPhone; CPU iPhone OS 8_0_2 like Mac OS X; Android 4.4) AppleWebKit/600.1.4 (KHTML, like Gecko) Version/8.0 Mobile/12A366 Safari/600.1.4";
+    const IOS_ANDROID: StaticStr    = "Mozilla/5.0 (iPhone; CPU iPhone OS 17_0 like Mac OS X; Android) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.0 Mobile/15E148 Safari/604.1";
+    const WP_81: StaticStr          = "Mozilla/5.0 (Mobile; Windows Phone 8.1; Android 4.0; ARM; Trident/7.0; Touch; rv:11.0; IEMobile/11.0; NOKIA; Lumia 635) like iPhone OS 7_0_3 Mac OS X AppleWebKit/537 (KHTML, like Gecko) Mobile Safari/537";
+    const SAFARI_26: StaticStr      = "Mozilla/5.0 (iPhone; CPU iPhone OS 18_6 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/26.0 Mobile/15E148 Safari/604.1";
+
+    fn violations(ua: &str) -> Vec<&'static str> {
+        ConsistencyChecker::default()
+            .check(&UserAgent::parse(ua))
+            .violations.iter().map(|rule| rule.id).collect()
+    }
+
+    #[test]
+    fn test_consistent() {
+        let report = ConsistencyChecker::default().check(&UserAgent::parse(CHROME_41));
+
+        assert!(report.is_consistent());
+        assert_eq!(report.spoof_likelihood, 0.0);
+    }
+
+    #[test]
+    fn test_impossible_combinations() {
+        assert_eq!(violations(CHROME_120_XP), vec!["modern-chrome-on-legacy-windows"]);
+        assert_eq!(violations(SAFARI_WINDOWS), vec!["safari-on-windows"]);
+        assert_eq!(violations(EDGE_MISMATCH), vec!["edge-chromium-mismatch"]);
+        assert_eq!(violations(IPHONE_ANDROID), vec!["iphone-with-android"]);
+        assert_eq!(violations(IOS_ANDROID), vec!["iphone-with-android"]);
+    }
+
+    #[test]
+    fn test_genuine_oddities() {
+        // Windows Phone 8.1 names both Android and iPhone
+        assert!(violations(WP_81).is_empty());
+        // Safari 26 freezes the OS version at 18.6
+        assert!(violations(SAFARI_26).is_empty());
+    }
+
+    #[test]
+    fn test_spoof_likelihood_combines_weights() {
+        let mut checker = ConsistencyChecker::default();
+        checker.add_rule(Rule {
+            id:          "no-xp",
+            description: "We don't see real XP traffic",
+            weight:      0.5,
+            violated:    |ua| ua.source.contains("Windows NT 5.1"),
+        });
+
+        let report = checker.check(&UserAgent::parse(CHROME_120_XP));
+        assert_eq!(report.violations.len(), 2);
+        assert!((report.spoof_likelihood - 0.85).abs() < 0.0001);
+    }
+}
@@ -0,0 +1,242 @@
+use std::fmt;
+
+use android_models;
+use apple_devices;
+use platform::{IpadDesktopMode, Platform};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FormFactor {
+    Desktop,
+    Phone,
+    Tablet,
+    Tv,
+    Unknown,
+    /// Smartwatches
+    Wearable,
+}
+
+impl fmt::Display for FormFactor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// Signals collected by the page itself (eg. from `navigator`) or sent as client hints that the
+/// caller passes along. Everything is optional.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ClientSignals {
+    /// `navigator.maxTouchPoints`
+    pub max_touch_points: Option<u32>,
+    /// `Sec-CH-DPR`, or `window.devicePixelRatio`
+    pub device_pixel_ratio: Option<f32>,
+    /// `Sec-CH-Viewport-Width`, in CSS pixels
+    pub viewport_width: Option<u32>,
+    /// `Sec-CH-Width`, the width the page wants an image at in device pixels
+    pub width: Option<u32>,
+}
+
+impl ClientSignals {
+    /// Record a client hint header. The legacy names without `Sec-CH-` are accepted too; unknown
+    /// headers and unparseable values are ignored.
+    pub fn apply_client_hint(&mut self, name: &str, value: &str) {
+        let name  = name.trim().to_lowercase();
+        let value = value.trim().trim_matches('"');
+
+        match name.trim_start_matches("sec-ch-") {
+            "dpr" => {
+                let dpr = value.parse::<f32>().ok().filter(|&dpr| dpr > 0.0);
+                self.device_pixel_ratio = dpr.or(self.device_pixel_ratio)
+            },
+            "viewport-width" => self.viewport_width = value.parse().ok().or(self.viewport_width),
+            "width"          => self.width = value.parse().ok().or(self.width),
+            _                => (),
+        }
+    }
+}
+
+/// What the device databases (`data/android_models.tsv`, `data/apple_devices.tsv`) know about a
+/// model
+#[derive(Debug, PartialEq)]
+pub struct ModelInfo {
+    pub brand: &'static str,
+    pub marketing_name: &'static str,
+    pub form_factor: FormFactor,
+    /// eg. "A13 Bionic", "Google Tensor"
+    pub chip: Option<&'static str>,
+    /// Device pixels per CSS pixel at the default display resolution
+    pub scale: Option<f32>,
+    /// In CSS pixels, in portrait
+    pub viewport_width: Option<u32>,
+    pub release_year: u16,
+}
+
+impl ModelInfo {
+    /// Brand and marketing name, eg. "Samsung Galaxy S21"
+    pub fn full_name(&self) -> String {
+        format!("{} {}", self.brand, self.marketing_name)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Device {
+    pub form_factor: FormFactor,
+    /// Mirrors `Platform::ipad_desktop_mode`. A confirmed iPad is a `FormFactor::Tablet`; a
+    /// possible one stays a `FormFactor::Desktop`.
+    pub ipad_desktop_mode: IpadDesktopMode,
+    /// The model code or hardware identifier as the user agent sends it (eg. `SM-G991B`,
+    /// `iPhone12,1`)
+    pub model: Option<String>,
+    /// From the device databases, if the model is in them
+    pub model_info: Option<&'static ModelInfo>,
+}
+
+/// Tokens for smart TVs, streaming sticks and set-top boxes
+const TV_TOKENS: [&str; 7] = [
+    "SMART-TV", "SmartTV", "Web0S", "AppleTV", "CrKey", "BRAVIA", "GoogleTV",
+];
+
+impl Device {
+    pub fn parse(ua: &str, platform: Option<&Platform>) -> Option<Device> {
+        use platform::PlatformName::*;
+
+        let ipad_desktop_mode = platform.map_or(IpadDesktopMode::No, |p| p.ipad_desktop_mode);
+
+        let (model, model_info) = match platform.map(|p| &p.name) {
+            Some(&Android) => {
+                let model = android_models::model_code(ua);
+                (model, model.and_then(android_models::lookup))
+            },
+            // Only apps send a hardware identifier, so skip the search for other platforms'
+            // browsers
+            Some(&IOS) | None => {
+                let model = apple_devices::identifier(ua);
+                (model, model.and_then(apple_devices::lookup))
+            },
+            _ if !ua.starts_with("Mozilla/") => {
+                let model = apple_devices::identifier(ua);
+                (model, model.and_then(apple_devices::lookup))
+            },
+            _ => (None, None),
+        };
+
+        let form_factor = if TV_TOKENS.iter().any(|token| ua.contains(token)) {
+            FormFactor::Tv
+        } else if let Some(info) = model_info {
+            info.form_factor
+        } else if ua.contains("iPad") || ua.contains("Tablet") || ipad_desktop_mode == IpadDesktopMode::Yes {
+            FormFactor::Tablet
+        } else if ua.contains("iPhone") || ua.contains("iPod") {
+            FormFactor::Phone
+        } else {
+            match platform.map(|p| &p.name) {
+                // Android phones' browsers have `Mobile` (Presto Opera `Opera Mobi`) in the user
+                // agent, tablets' don't. Native apps (`Dalvik/2.1.0 (Linux; U; Android 11; Pixel
+                // 5)`) never send it.
+                Some(&Android) | Some(&HarmonyOS) => {
+                    if ua.contains("Mobile") || ua.contains("Opera Mobi") {
+                        FormFactor::Phone
+                    } else if ua.starts_with("Mozilla/") || ua.starts_with("Opera/") {
+                        FormFactor::Tablet
+                    } else {
+                        FormFactor::Unknown
+                    }
+                },
+                Some(&BlackBerry) | Some(&FirefoxOS) | Some(&KaiOS) | Some(&Sailfish) |
+                Some(&Symbian) | Some(&WindowsPhone) => FormFactor::Phone,
+                Some(&Tizen) | Some(&WebOS) => {
+                    if ua.contains("Mobile") { FormFactor::Phone } else { FormFactor::Unknown }
+                },
+                Some(&ChromeOS) | Some(&FreeBSD) | Some(&Linux) | Some(&Mac) | Some(&NetBSD) |
+                Some(&OpenBSD) | Some(&Solaris) | Some(&Windows) => FormFactor::Desktop,
+                Some(&IOS) => FormFactor::Unknown,
+                None => return None,
+            }
+        };
+
+        Some(Device {
+            form_factor:       form_factor,
+            ipad_desktop_mode: ipad_desktop_mode,
+            model:             model.map(str::to_owned),
+            model_info:        model_info,
+        })
+    }
+
+    pub fn is_mobile(&self) -> bool {
+        self.form_factor == FormFactor::Phone || self.form_factor == FormFactor::Tablet
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use platform::IpadDesktopMode;
+    use user_agent::UserAgent;
+    use super::{ClientSignals, FormFactor};
+
+    type StaticStr = &'static str;
+
+    const IPHONE: StaticStr         = "Mozilla/5.0 (iPhone; CPU iPhone OS 8_0_2 like Mac OS X) AppleWebKit/600.1.4 (KHTML, like Gecko) Version/8.0 Mobile/12A366 Safari/600.1.4";
+    const ANDROID_TABLET: StaticStr = "Mozilla/5.0 (Linux; Android 13; SM-X700) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+    const SAFARI_MAC: StaticStr     = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.0 Safari/605.1.15";
+    const TIZEN_TV: StaticStr       = "Mozilla/5.0 (SMART-TV; LINUX; Tizen 6.0) AppleWebKit/537.36 (KHTML, like Gecko) 76.0.3809.146/6.0 TV Safari/537.36";
+    const DALVIK: StaticStr         = "Dalvik/2.1.0 (Linux; U; Android 11; Pixel 5 Build/RQ3A.210805.001.A1)";
+    const IOS_SDK: StaticStr        = "MyApp/2.0 (iPhone12,1; iOS 14.2; Scale/2.00)";
+
+    fn form_factor(ua: &str) -> FormFactor {
+        UserAgent::parse(ua).device.unwrap().form_factor
+    }
+
+    #[test]
+    fn test_form_factors() {
+        assert_eq!(form_factor(IPHONE), FormFactor::Phone);
+        assert_eq!(form_factor(ANDROID_TABLET), FormFactor::Tablet);
+        assert_eq!(form_factor(TIZEN_TV), FormFactor::Tv);
+        assert!(UserAgent::parse("curl/7.68.0").device.is_none());
+    }
+
+    #[test]
+    fn test_android_model() {
+        let device = UserAgent::parse(DALVIK).device.unwrap();
+
+        // Apps don't send `Mobile`, so only the model says it's a phone
+        assert_eq!(device.form_factor, FormFactor::Phone);
+        assert_eq!(device.model.as_deref(), Some("Pixel 5"));
+        assert_eq!(device.model_info.unwrap().full_name(), "Google Pixel 5");
+
+        let tablet = UserAgent::parse(ANDROID_TABLET).device.unwrap();
+        assert_eq!(tablet.model_info.map(|info| info.marketing_name), Some("Galaxy Tab S8"));
+    }
+
+    #[test]
+    fn test_apple_model() {
+        let device = UserAgent::parse(IOS_SDK).device.unwrap();
+
+        assert_eq!(device.form_factor, FormFactor::Phone);
+        assert_eq!(device.model.as_deref(), Some("iPhone12,1"));
+        assert_eq!(device.model_info.unwrap().full_name(), "Apple iPhone 11");
+        assert_eq!(device.model_info.unwrap().chip, Some("A13 Bionic"));
+
+        // watchOS isn't a platform this crate detects, but the identifier is enough
+        assert_eq!(form_factor("MyApp/2.0 (Watch6,2; watchOS 7.0)"), FormFactor::Wearable);
+
+        // Identifier-like text in another platform's browser isn't a model
+        let windows = UserAgent::parse("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36 Watch6,2");
+        assert_eq!(windows.device.map(|device| device.model), Some(None));
+    }
+
+    #[test]
+    fn test_ipad_in_desktop_mode() {
+        let mut ua = UserAgent::parse(SAFARI_MAC);
+        {
+            let device = ua.device.as_ref().unwrap();
+            assert_eq!(device.form_factor, FormFactor::Desktop);
+            assert_eq!(device.ipad_desktop_mode, IpadDesktopMode::Possibly);
+        }
+
+        ua.apply_client_signals(&ClientSignals { max_touch_points: Some(5), ..ClientSignals::default() });
+
+        let device = ua.device.unwrap();
+        assert_eq!(device.form_factor, FormFactor::Tablet);
+        assert_eq!(device.ipad_desktop_mode, IpadDesktopMode::Yes);
+        assert_eq!(ua.platform.unwrap().ipad_desktop_mode, IpadDesktopMode::Yes);
+    }
+}
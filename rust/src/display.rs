@@ -0,0 +1,169 @@
+use app::App;
+use device::{ClientSignals, ModelInfo};
+use precision::Precision;
+use tokenizer::tokenize;
+
+/// Screen density and size, for picking image sizes. Widths are in pixels: `viewport_width` in
+/// CSS pixels and `width` (the size the page wants an image at) in device pixels.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Display {
+    /// Device pixels per CSS pixel
+    pub scale: Option<f32>,
+    pub viewport_width: Option<u32>,
+    pub width: Option<u32>,
+    /// `Defaulted` when a value came from the model's defaults rather than the client
+    pub precision: Precision,
+    scale_defaulted: bool,
+    viewport_width_defaulted: bool,
+}
+
+/// `Scale/3.00` in a comment, as AFNetworking-style iOS apps send it
+fn parse_scale(ua: &str) -> Option<f32> {
+    tokenize(ua).comments()
+        .flat_map(|comment| comment.items())
+        .filter_map(|item| {
+            let scale = item.text.strip_prefix("Scale/")?;
+            scale.parse::<f32>().ok()
+        })
+        .find(|&scale| scale > 0.0)
+}
+
+impl Display {
+    fn empty() -> Display {
+        Display {
+            scale:                    None,
+            viewport_width:           None,
+            width:                    None,
+            precision:                Precision::Exact,
+            scale_defaulted:          false,
+            viewport_width_defaulted: false,
+        }
+    }
+
+    /// Explicit values from the user agent, with the rest filled in from the device database's
+    /// defaults for the model (`Device::model_info`). `None` if there's neither.
+    pub fn parse(ua: &str, app: Option<&App>, model_info: Option<&ModelInfo>) -> Option<Display> {
+        let scale          = app.and_then(|app| app.scale).or_else(|| parse_scale(ua));
+        let default_scale  = model_info.and_then(|info| info.scale);
+        let viewport_width = model_info.and_then(|info| info.viewport_width);
+
+        if scale.is_none() && default_scale.is_none() && viewport_width.is_none() { return None }
+
+        let mut display = Display::empty();
+        display.scale = scale;
+
+        if display.scale.is_none() && default_scale.is_some() {
+            display.scale           = default_scale;
+            display.scale_defaulted = true;
+        }
+        if viewport_width.is_some() {
+            display.viewport_width           = viewport_width;
+            display.viewport_width_defaulted = true;
+        }
+
+        display.update_precision();
+        Some(display)
+    }
+
+    /// Only the values the client sent, from its client hints
+    pub fn from_client_signals(signals: &ClientSignals) -> Option<Display> {
+        if signals.device_pixel_ratio.is_none() && signals.viewport_width.is_none() && signals.width.is_none() {
+            return None
+        }
+
+        let mut display = Display::empty();
+        display.apply_client_signals(signals);
+
+        Some(display)
+    }
+
+    /// Client hints are explicit, so they replace anything parsed or defaulted
+    pub fn apply_client_signals(&mut self, signals: &ClientSignals) {
+        if let Some(dpr) = signals.device_pixel_ratio {
+            self.scale           = Some(dpr);
+            self.scale_defaulted = false;
+        }
+        if let Some(viewport_width) = signals.viewport_width {
+            self.viewport_width           = Some(viewport_width);
+            self.viewport_width_defaulted = false;
+        }
+        if let Some(width) = signals.width {
+            self.width = Some(width)
+        }
+
+        self.update_precision()
+    }
+
+    fn update_precision(&mut self) {
+        self.precision = if self.scale_defaulted || self.viewport_width_defaulted {
+            Precision::Defaulted
+        } else {
+            Precision::Exact
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use device::ClientSignals;
+    use precision::Precision;
+    use user_agent::UserAgent;
+
+    type StaticStr = &'static str;
+
+    const IOS_SDK: StaticStr        = "AppName/1.2.3 (iPhone12,1; iOS 14.2; Scale/3.00)";
+    const DALVIK: StaticStr         = "Dalvik/2.1.0 (Linux; U; Android 11; Pixel 5 Build/RQ3A.210805.001.A1)";
+    const CHROME: StaticStr         = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+    const CHROME_PIXEL_7: StaticStr = "Mozilla/5.0 (Linux; Android 13; Pixel 7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/116.0.0.0 Mobile Safari/537.36";
+    const CHROME_GALAXY: StaticStr  = "Mozilla/5.0 (Linux; Android 11; SM-G991B) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/116.0.0.0 Mobile Safari/537.36";
+
+    #[test]
+    fn test_scale_from_user_agent() {
+        let display = UserAgent::parse(IOS_SDK).display.unwrap();
+
+        assert_eq!(display.scale, Some(3.0));
+        // The viewport width is still the iPhone 11's default
+        assert_eq!((display.viewport_width, display.precision), (Some(414), Precision::Defaulted));
+
+        assert_eq!(UserAgent::parse("Foo/1.0 (Custom; Scale/2.00)").display.unwrap().scale, Some(2.0));
+        assert!(UserAgent::parse(CHROME).display.is_none());
+    }
+
+    #[test]
+    fn test_model_defaults() {
+        let display = UserAgent::parse(DALVIK).display.unwrap();
+
+        assert_eq!((display.scale, display.viewport_width), (Some(2.75), Some(393)));
+        assert_eq!(display.precision, Precision::Defaulted);
+
+        // Browsers name the model too
+        let display = UserAgent::parse(CHROME_PIXEL_7).display.unwrap();
+        assert_eq!((display.scale, display.viewport_width), (Some(2.625), Some(412)));
+
+        // Known models whose resolution the user can change have no defaults
+        assert!(UserAgent::parse(CHROME_GALAXY).device.unwrap().model_info.is_some());
+        assert!(UserAgent::parse(CHROME_GALAXY).display.is_none());
+        // Nor does a bare `iPhone`, which could be any generation
+        assert!(UserAgent::parse("AppName/1.2.3 (iPhone; iOS 14.2)").display.is_none());
+    }
+
+    #[test]
+    fn test_client_hints() {
+        let mut signals = ClientSignals::default();
+        signals.apply_client_hint("Sec-CH-DPR", "2");
+        signals.apply_client_hint("sec-ch-viewport-width", "1280");
+        signals.apply_client_hint("Sec-CH-Width", "640");
+        signals.apply_client_hint("Sec-CH-DPR", "lots");
+
+        let mut chrome = UserAgent::parse(CHROME);
+        chrome.apply_client_signals(&signals);
+
+        let display = chrome.display.unwrap();
+        assert_eq!((display.scale, display.viewport_width, display.width), (Some(2.0), Some(1280), Some(640)));
+        assert_eq!(display.precision, Precision::Exact);
+
+        let mut ios = UserAgent::parse(IOS_SDK);
+        ios.apply_client_signals(&ClientSignals { viewport_width: Some(428), ..ClientSignals::default() });
+        assert_eq!(ios.display.unwrap().precision, Precision::Exact);
+    }
+}
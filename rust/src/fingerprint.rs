@@ -0,0 +1,284 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+use std::str::FromStr;
+
+use browser::BrowserFamily;
+use user_agent::UserAgent;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FingerprintKind {
+    Ja3,
+    Ja4,
+    /// HTTP/2 SETTINGS, WINDOW_UPDATE and pseudo-header order (Akamai format)
+    Http2,
+}
+
+impl FromStr for FingerprintKind {
+    type Err = ();
+
+    fn from_str(string: &str) -> Result<FingerprintKind, ()> {
+        match string {
+            "ja3" => Ok(FingerprintKind::Ja3),
+            "ja4" => Ok(FingerprintKind::Ja4),
+            "h2"  => Ok(FingerprintKind::Http2),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Fingerprints computed for a connection by the edge. Any of them may be missing.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Fingerprints<'a> {
+    pub ja3: Option<&'a str>,
+    pub ja4: Option<&'a str>,
+    pub http2: Option<&'a str>,
+}
+
+/// A browser family and (inclusive) major version range known to produce a fingerprint.
+/// Missing bounds are open-ended.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FingerprintEntry {
+    pub family: BrowserFamily,
+    pub min_version: Option<i16>,
+    pub max_version: Option<i16>,
+}
+
+impl FingerprintEntry {
+    fn matches(&self, family: &BrowserFamily, version: i16) -> bool {
+        self.family == *family &&
+            self.min_version.is_none_or(|min| version >= min) &&
+            self.max_version.is_none_or(|max| version <= max)
+    }
+}
+
+/// The fingerprint disagreed with the claimed browser
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mismatch {
+    pub kind: FingerprintKind,
+    /// Families that are known to produce this fingerprint
+    pub known_families: Vec<BrowserFamily>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Plausibility {
+    /// At least one fingerprint matched the claimed browser and none contradicted it
+    Plausible,
+    /// At least one fingerprint is known to belong to other browsers or versions
+    Implausible(Vec<Mismatch>),
+    /// None of the fingerprints are in the database, or the user agent isn't a browser
+    Unknown,
+}
+
+/// Maps TLS and HTTP/2 fingerprints to the browsers that produce them.
+///
+/// The database is plain text with one entry per line: the kind (`ja3`, `ja4` or `h2`), the
+/// fingerprint, the browser family and a major version or range (`79-`, `-110`, `100-120`, `*`),
+/// separated by tabs. Blank lines and lines starting with `#` are ignored. A fingerprint can be
+/// listed more than once, since Chromium-based browsers share their TLS stack.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FingerprintDb {
+    entries: HashMap<(FingerprintKind, String), Vec<FingerprintEntry>>,
+}
+
+fn invalid_data(line_number: usize, message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("line {}: {}", line_number, message))
+}
+
+fn parse_version_range(range: &str) -> Option<(Option<i16>, Option<i16>)> {
+    if range == "*" { return Some((None, None)) }
+    if !range.contains('-') {
+        let version = i16::from_str(range).ok()?;
+        return Some((Some(version), Some(version)))
+    }
+
+    let mut bounds = range.splitn(2, '-');
+    let bound = |bound: Option<&str>| match bound {
+        Some("") | None => Some(None),
+        Some(version) => i16::from_str(version).ok().map(Some),
+    };
+
+    let min = bound(bounds.next())?;
+    let max = bound(bounds.next())?;
+
+    Some((min, max))
+}
+
+impl FingerprintDb {
+    pub fn parse(string: &str) -> io::Result<FingerprintDb> {
+        let mut db = FingerprintDb::default();
+
+        for (index, line) in string.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') { continue }
+
+            let columns: Vec<&str> = line.split('\t').map(str::trim).filter(|c| !c.is_empty()).collect();
+            if columns.len() != 4 {
+                return Err(invalid_data(index + 1, "expected 4 tab-separated columns"))
+            }
+
+            let kind = FingerprintKind::from_str(columns[0])
+                .map_err(|_| invalid_data(index + 1, "unknown fingerprint kind"))?;
+            let family = BrowserFamily::from_str(columns[2])
+                .map_err(|_| invalid_data(index + 1, "unknown browser family"))?;
+            let (min, max) = parse_version_range(columns[3])
+                .ok_or_else(|| invalid_data(index + 1, "invalid version range"))?;
+
+            db.insert(kind, columns[1], FingerprintEntry {
+                family:      family,
+                min_version: min,
+                max_version: max,
+            });
+        }
+
+        Ok(db)
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<FingerprintDb> {
+        let mut string = String::new();
+        File::open(path)?.read_to_string(&mut string)?;
+
+        FingerprintDb::parse(&string)
+    }
+
+    pub fn insert(&mut self, kind: FingerprintKind, fingerprint: &str, entry: FingerprintEntry) {
+        self.entries
+            .entry((kind, fingerprint.to_owned()))
+            .or_default()
+            .push(entry)
+    }
+
+    pub fn lookup(&self, kind: FingerprintKind, fingerprint: &str) -> Option<&[FingerprintEntry]> {
+        self.entries
+            .get(&(kind, fingerprint.trim().to_owned()))
+            .map(|entries| entries.as_slice())
+    }
+
+    /// Whether the TLS/HTTP2 client is plausible for the browser the user agent claims to be.
+    pub fn check(&self, ua: &UserAgent, fingerprints: &Fingerprints) -> Plausibility {
+        let browser = match ua.browser {
+            Some(ref browser) => browser,
+            None => return Plausibility::Unknown,
+        };
+
+        let given = [
+            (FingerprintKind::Ja3,   fingerprints.ja3),
+            (FingerprintKind::Ja4,   fingerprints.ja4),
+            (FingerprintKind::Http2, fingerprints.http2),
+        ];
+
+        let mut matched    = false;
+        let mut mismatches = vec![];
+
+        for &(kind, fingerprint) in given.iter() {
+            let entries = match fingerprint.and_then(|f| self.lookup(kind, f)) {
+                Some(entries) => entries,
+                None => continue,
+            };
+
+            if entries.iter().any(|e| e.matches(&browser.family, browser.major_version)) {
+                matched = true;
+            } else {
+                let mut known_families: Vec<BrowserFamily> = vec![];
+                for entry in entries {
+                    if !known_families.contains(&entry.family) {
+                        known_families.push(entry.family.clone())
+                    }
+                }
+
+                mismatches.push(Mismatch {
+                    kind:           kind,
+                    known_families: known_families,
+                });
+            }
+        }
+
+        if !mismatches.is_empty() {
+            Plausibility::Implausible(mismatches)
+        } else if matched {
+            Plausibility::Plausible
+        } else {
+            Plausibility::Unknown
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use browser::BrowserFamily;
+    use user_agent::UserAgent;
+    use super::{FingerprintDb, FingerprintKind, Fingerprints, Mismatch, Plausibility};
+
+    type StaticStr = &'static str;
+
+    const DB: StaticStr = "
+# kind\tfingerprint\tfamily\tversions
+ja3\tcd08e31494f9531f560d64c695473da9\tChrome\t79-
+ja3\t579ccef312d18482fc42e2b822ca2430\tFirefox\t*
+ja4\tt13d1516h2_8daaf6152771_02713d6af862\tChrome\t100-
+h2\t1:65536;2:0;4:6291456;6:262144|15663105|0|m,a,s,p\tChrome\t106-
+";
+
+    const CHROME_120: StaticStr  = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+    const CHROME_41: StaticStr   = "Mozilla/5.0 (Windows NT 6.1) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/41.0.2228.0 Safari/537.36";
+    const FIREFOX_40: StaticStr  = "Mozilla/5.0 (Windows NT 6.1; WOW64; rv:40.0) Gecko/20100101 Firefox/40.1";
+    const CHROME_JA3: StaticStr  = "cd08e31494f9531f560d64c695473da9";
+    const FIREFOX_JA3: StaticStr = "579ccef312d18482fc42e2b822ca2430";
+
+    fn db() -> FingerprintDb {
+        FingerprintDb::parse(DB).unwrap()
+    }
+
+    #[test]
+    fn test_plausible() {
+        let fingerprints = Fingerprints {
+            ja3:   Some(CHROME_JA3),
+            ja4:   Some("t13d1516h2_8daaf6152771_02713d6af862"),
+            http2: Some("1:65536;2:0;4:6291456;6:262144|15663105|0|m,a,s,p"),
+        };
+
+        assert_eq!(db().check(&UserAgent::parse(CHROME_120), &fingerprints), Plausibility::Plausible)
+    }
+
+    #[test]
+    fn test_wrong_family() {
+        let fingerprints = Fingerprints { ja3: Some(FIREFOX_JA3), ..Fingerprints::default() };
+
+        assert_eq!(
+            db().check(&UserAgent::parse(CHROME_120), &fingerprints),
+            Plausibility::Implausible(vec![Mismatch {
+                kind:           FingerprintKind::Ja3,
+                known_families: vec![BrowserFamily::Firefox],
+            }])
+        )
+    }
+
+    #[test]
+    fn test_wrong_version() {
+        let fingerprints = Fingerprints { ja3: Some(CHROME_JA3), ..Fingerprints::default() };
+
+        match db().check(&UserAgent::parse(CHROME_41), &fingerprints) {
+            Plausibility::Implausible(ref mismatches) => {
+                assert_eq!(mismatches[0].known_families, vec![BrowserFamily::Chrome])
+            },
+            other => panic!("expected Implausible, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_unknown() {
+        let fingerprints = Fingerprints { ja3: Some("00000000000000000000000000000000"), ..Fingerprints::default() };
+
+        assert_eq!(db().check(&UserAgent::parse(FIREFOX_40), &fingerprints), Plausibility::Unknown);
+        assert_eq!(db().check(&UserAgent::parse("curl/7.68.0"), &fingerprints), Plausibility::Unknown);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(FingerprintDb::parse("ja3\tabc\tChrome").is_err());
+        assert!(FingerprintDb::parse("ja5\tabc\tChrome\t*").is_err());
+        assert!(FingerprintDb::parse("ja3\tabc\tNetscape\t*").is_err());
+        assert!(FingerprintDb::parse("ja3\tabc\tChrome\tnew").is_err());
+    }
+}
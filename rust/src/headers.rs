@@ -0,0 +1,229 @@
+use browser::BrowserFamily;
+use library::LibraryName;
+use user_agent::UserAgent;
+
+/// The request headers a browser family sends on a top-level navigation, from `min_version`
+/// onwards. Header names are lowercase.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HeaderProfile {
+    pub family: BrowserFamily,
+    pub min_version: i16,
+    /// Headers that are always sent
+    pub required: &'static [&'static str],
+    /// The relative order of every header the browser sends (including optional ones)
+    pub order: &'static [&'static str],
+}
+
+/// The exact header names, in order, that an HTTP library sends by default
+#[derive(Clone, Debug, PartialEq)]
+pub struct LibrarySignature {
+    pub name: LibraryName,
+    pub headers: &'static [&'static str],
+}
+
+const CHROME_89: &[&str] = &[
+    "sec-ch-ua", "sec-ch-ua-mobile", "sec-ch-ua-platform", "upgrade-insecure-requests",
+    "user-agent", "accept", "sec-fetch-site", "sec-fetch-mode", "sec-fetch-user",
+    "sec-fetch-dest", "accept-encoding", "accept-language",
+];
+const CHROME_76: &[&str] = &[
+    "upgrade-insecure-requests", "user-agent", "accept", "sec-fetch-site", "sec-fetch-mode",
+    "sec-fetch-user", "sec-fetch-dest", "accept-encoding", "accept-language",
+];
+const CHROME: &[&str] = &[
+    "upgrade-insecure-requests", "user-agent", "accept", "accept-encoding", "accept-language",
+];
+const FIREFOX_90: &[&str] = &[
+    "user-agent", "accept", "accept-language", "accept-encoding", "upgrade-insecure-requests",
+    "sec-fetch-dest", "sec-fetch-mode", "sec-fetch-site", "sec-fetch-user",
+];
+const FIREFOX: &[&str] = &[
+    "user-agent", "accept", "accept-language", "accept-encoding", "upgrade-insecure-requests",
+];
+const SAFARI_17: &[&str] = &[
+    "accept", "sec-fetch-site", "sec-fetch-dest", "accept-language", "sec-fetch-mode",
+    "user-agent", "accept-encoding",
+];
+const SAFARI: &[&str] = &[
+    "accept", "user-agent", "accept-language", "accept-encoding",
+];
+
+const CHROME_89_REQUIRED: &[&str] = &[
+    "sec-ch-ua", "sec-ch-ua-mobile", "user-agent", "accept", "sec-fetch-site", "sec-fetch-mode",
+    "sec-fetch-dest", "accept-encoding", "accept-language",
+];
+const CHROME_76_REQUIRED: &[&str] = &[
+    "user-agent", "accept", "sec-fetch-site", "sec-fetch-mode", "accept-encoding",
+    "accept-language",
+];
+const FIREFOX_90_REQUIRED: &[&str] = &[
+    "user-agent", "accept", "accept-language", "accept-encoding", "sec-fetch-dest",
+    "sec-fetch-mode", "sec-fetch-site",
+];
+const BASIC_REQUIRED: &[&str] = &["user-agent", "accept", "accept-language", "accept-encoding"];
+
+/// Reference profiles; for each family the last profile whose `min_version` is at or below the
+/// browser's version is used. Chromium-based Edge parses as Chrome and sends Chrome's headers.
+pub const PROFILES: &[HeaderProfile] = &[
+    HeaderProfile { family: BrowserFamily::Chrome,       min_version: 0,  required: BASIC_REQUIRED,      order: CHROME },
+    HeaderProfile { family: BrowserFamily::Chrome,       min_version: 76, required: CHROME_76_REQUIRED,  order: CHROME_76 },
+    HeaderProfile { family: BrowserFamily::Chrome,       min_version: 89, required: CHROME_89_REQUIRED,  order: CHROME_89 },
+    HeaderProfile { family: BrowserFamily::Firefox,      min_version: 0,  required: BASIC_REQUIRED,      order: FIREFOX },
+    HeaderProfile { family: BrowserFamily::Firefox,      min_version: 90, required: FIREFOX_90_REQUIRED, order: FIREFOX_90 },
+    HeaderProfile { family: BrowserFamily::Safari,       min_version: 0,  required: BASIC_REQUIRED,      order: SAFARI },
+    HeaderProfile { family: BrowserFamily::Safari,       min_version: 17, required: BASIC_REQUIRED,      order: SAFARI_17 },
+    HeaderProfile { family: BrowserFamily::MobileSafari, min_version: 0,  required: BASIC_REQUIRED,      order: SAFARI },
+    HeaderProfile { family: BrowserFamily::MobileSafari, min_version: 17, required: BASIC_REQUIRED,      order: SAFARI_17 },
+];
+
+pub const LIBRARY_SIGNATURES: &[LibrarySignature] = &[
+    LibrarySignature { name: LibraryName::PythonRequests, headers: &["host", "user-agent", "accept-encoding", "accept", "connection"] },
+    LibrarySignature { name: LibraryName::Curl,           headers: &["host", "user-agent", "accept"] },
+    LibrarySignature { name: LibraryName::GoHttpClient,   headers: &["host", "user-agent", "accept-encoding"] },
+    LibrarySignature { name: LibraryName::Okhttp,         headers: &["host", "connection", "accept-encoding", "user-agent"] },
+    LibrarySignature { name: LibraryName::Axios,          headers: &["accept", "user-agent", "accept-encoding", "host", "connection"] },
+];
+
+/// Headers that are added by proxies, depend on the request or are set by the application, so
+/// they say nothing about the client.
+fn is_ignored(name: &str) -> bool {
+    name.starts_with(':') || name.starts_with("x-") ||
+        ["authorization", "cache-control", "content-length", "content-type", "cookie",
+         "forwarded", "origin", "pragma", "referer", "via"].contains(&name)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum HeaderFinding {
+    /// A header the claimed browser always sends is missing
+    Missing(&'static str),
+    /// The first header was sent after the second, which the claimed browser never does
+    OutOfOrder(String, String),
+    /// The headers exactly match an HTTP library's defaults
+    LibrarySignature(LibraryName),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct HeaderReport {
+    pub findings: Vec<HeaderFinding>,
+    /// Whether a reference profile was available for the claimed browser
+    pub has_profile: bool,
+}
+
+impl HeaderReport {
+    pub fn is_consistent(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    pub fn library(&self) -> Option<&LibraryName> {
+        self.findings.iter()
+            .filter_map(|finding| match *finding {
+                HeaderFinding::LibrarySignature(ref name) => Some(name),
+                _ => None,
+            })
+            .next()
+    }
+}
+
+pub fn profile_for(family: &BrowserFamily, version: i16) -> Option<&'static HeaderProfile> {
+    PROFILES.iter()
+        .rev()
+        .find(|profile| profile.family == *family && profile.min_version <= version)
+}
+
+/// Check the names of the request headers, in the order they were received, against the
+/// reference profile for the browser the user agent claims to be.
+pub fn check_headers(ua: &UserAgent, headers: &[&str]) -> HeaderReport {
+    let names: Vec<String> = headers.iter()
+        .map(|name| name.trim().to_lowercase())
+        .filter(|name| !is_ignored(name))
+        .collect();
+
+    let mut findings = vec![];
+
+    for signature in LIBRARY_SIGNATURES {
+        if names.iter().map(String::as_str).eq(signature.headers.iter().cloned()) {
+            findings.push(HeaderFinding::LibrarySignature(signature.name.clone()));
+            break
+        }
+    }
+
+    let profile = ua.browser.as_ref().and_then(|b| profile_for(&b.family, b.major_version));
+
+    if let Some(profile) = profile {
+        for required in profile.required {
+            if !names.iter().any(|name| name == required) {
+                findings.push(HeaderFinding::Missing(required))
+            }
+        }
+
+        let positions: Vec<(usize, &String)> = names.iter()
+            .filter_map(|name| profile.order.iter().position(|o| o == name).map(|p| (p, name)))
+            .collect();
+
+        for pair in positions.windows(2) {
+            if pair[0].0 > pair[1].0 {
+                findings.push(HeaderFinding::OutOfOrder(pair[0].1.clone(), pair[1].1.clone()))
+            }
+        }
+    }
+
+    HeaderReport {
+        findings:    findings,
+        has_profile: profile.is_some(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use library::LibraryName;
+    use user_agent::UserAgent;
+    use super::{check_headers, HeaderFinding};
+
+    type StaticStr = &'static str;
+
+    const CHROME_120: StaticStr = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+    const FIREFOX_40: StaticStr = "Mozilla/5.0 (Windows NT 6.1; WOW64; rv:40.0) Gecko/20100101 Firefox/40.1";
+    const EDGE_120: StaticStr   = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36 Edg/120.0.0.0";
+
+    const CHROME_HEADERS: &[&str] = &[
+        ":method", ":authority", ":scheme", ":path", "sec-ch-ua", "sec-ch-ua-mobile",
+        "sec-ch-ua-platform", "upgrade-insecure-requests", "user-agent", "accept",
+        "sec-fetch-site", "sec-fetch-mode", "sec-fetch-user", "sec-fetch-dest", "accept-encoding",
+        "accept-language", "cookie",
+    ];
+    const REQUESTS_HEADERS: &[&str] = &["Host", "User-Agent", "Accept-Encoding", "Accept", "Connection"];
+
+    #[test]
+    fn test_genuine_chrome() {
+        let report = check_headers(&UserAgent::parse(CHROME_120), CHROME_HEADERS);
+
+        assert!(report.has_profile);
+        assert!(report.is_consistent());
+
+        // Chromium-based Edge sends the same headers
+        assert!(check_headers(&UserAgent::parse(EDGE_120), CHROME_HEADERS).is_consistent());
+    }
+
+    #[test]
+    fn test_python_requests_claiming_chrome() {
+        let report = check_headers(&UserAgent::parse(CHROME_120), REQUESTS_HEADERS);
+
+        assert_eq!(report.library(), Some(&LibraryName::PythonRequests));
+        assert!(report.findings.contains(&HeaderFinding::Missing("sec-ch-ua")));
+        assert!(report.findings.contains(&HeaderFinding::Missing("accept-language")));
+        assert!(report.findings.contains(
+            &HeaderFinding::OutOfOrder("accept-encoding".to_owned(), "accept".to_owned())
+        ));
+    }
+
+    #[test]
+    fn test_old_firefox_order() {
+        let headers = ["Host", "User-Agent", "Accept-Language", "Accept", "Accept-Encoding"];
+        let report  = check_headers(&UserAgent::parse(FIREFOX_40), &headers);
+
+        assert_eq!(
+            report.findings,
+            vec![HeaderFinding::OutOfOrder("accept-language".to_owned(), "accept".to_owned())]
+        )
+    }
+}
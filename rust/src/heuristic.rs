@@ -0,0 +1,215 @@
+/// Words that (case-insensitively) give away an automated client. They only count at the start
+/// of a word (`acme-scraper`, `SomeNewBot`) or at the end of a product name (`feedfetcher/2.0`),
+/// so that model codes like `CUBOT_X19` don't match.
+const KEYWORDS: [&str; 6] = ["bot", "crawler", "spider", "scraper", "fetch", "slurp"];
+
+/// Rendering engines' product tokens. A user agent with one of these is built like a browser's,
+/// so a keyword alone isn't enough to call it a bot.
+const ENGINE_TOKENS: [&str; 4] = ["AppleWebKit/", "Gecko/", "Trident/", "Presto/"];
+
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Ord)]
+pub enum Confidence {
+    Low,
+    Medium,
+    High,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Evidence {
+    /// One of the `KEYWORDS` appeared in the user agent
+    Keyword(&'static str),
+    /// A URL, usually pointing at the crawler's documentation
+    Url,
+    /// A contact email address
+    Email,
+    /// Doesn't start with the `Mozilla/5.0 (...)` structure that every mainstream browser sends
+    NoMozillaPrefix,
+}
+
+impl Evidence {
+    fn weight(&self, browser_like: bool) -> u8 {
+        use self::Evidence::*;
+
+        match *self {
+            Keyword(_) if browser_like => 1,
+            Keyword(_)      => 2,
+            Url             => 2,
+            Email           => 2,
+            NoMozillaPrefix => 1,
+        }
+    }
+}
+
+/// The result of guessing whether a user agent that isn't in the bot catalog is automated.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HeuristicMatch {
+    pub confidence: Confidence,
+    pub evidence: Vec<Evidence>,
+}
+
+/// Offsets where `needle` (which must be lowercase) occurs in `bytes`, ignoring ASCII case
+fn find_ignore_case<'a>(bytes: &'a [u8], needle: &'a [u8]) -> impl Iterator<Item = usize> + 'a {
+    bytes.windows(needle.len())
+        .enumerate()
+        .filter(move |&(_, window)| window.eq_ignore_ascii_case(needle))
+        .map(|(offset, _)| offset)
+}
+
+fn is_keyword_at(bytes: &[u8], start: usize, len: usize) -> bool {
+    // Allow plurals and agent nouns (`spiders`, `Fetcher`), but not longer words (`botany`)
+    let rest = &bytes[start + len..];
+    let rest = [&b"ers"[..], b"er", b"s"].iter()
+        .find(|suffix| rest.len() >= suffix.len() && rest[..suffix.len()].eq_ignore_ascii_case(suffix))
+        .map_or(rest, |suffix| &rest[suffix.len()..]);
+
+    // A product name ending in the keyword (`feedfetcher/2.0`)
+    if rest.first() == Some(&b'/') { return true }
+
+    let starts_word = start == 0 || {
+        let (before, first) = (bytes[start - 1], bytes[start]);
+        !before.is_ascii_alphanumeric() || (before.is_ascii_lowercase() && first.is_ascii_uppercase())
+    };
+
+    starts_word && rest.first().is_none_or(|byte| !byte.is_ascii_lowercase())
+}
+
+fn has_url(bytes: &[u8]) -> bool {
+    [&b"http://"[..], b"https://", b"www."].iter().any(|needle| find_ignore_case(bytes, needle).next().is_some())
+}
+
+/// Something like `name@example.com`: a word character before the `@`, and a dot after it
+/// between two word characters
+fn has_email(bytes: &[u8]) -> bool {
+    let is_word = |byte: u8| byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'-';
+
+    bytes.iter().enumerate().filter(|&(_, &byte)| byte == b'@').any(|(at, _)| {
+        let rest   = &bytes[at + 1..];
+        let domain = &rest[..rest.iter().take_while(|&&b| is_word(b) || b == b'.').count()];
+        let dot    = domain.iter().position(|&b| b == b'.');
+
+        at > 0 && is_word(bytes[at - 1]) && dot.is_some_and(|dot| {
+            dot > 0 && domain.get(dot + 1).is_some_and(|&b| is_word(b))
+        })
+    })
+}
+
+/// `Mozilla/<major>.<minor> (`
+fn has_mozilla_prefix(ua: &str) -> bool {
+    let rest = match ua.strip_prefix("Mozilla/") {
+        Some(rest) => rest.as_bytes(),
+        None       => return false,
+    };
+
+    let major = rest.iter().take_while(|b| b.is_ascii_digit()).count();
+    if major == 0 || rest.get(major) != Some(&b'.') { return false }
+
+    let rest  = &rest[major + 1..];
+    let minor = rest.iter().take_while(|b| b.is_ascii_digit()).count();
+
+    minor > 0 && rest[minor..].starts_with(b" (")
+}
+
+impl HeuristicMatch {
+    /// Look for tell-tale signs of a bot. Returns `None` if there's no evidence at all.
+    pub fn analyze(ua: &str) -> Option<HeuristicMatch> {
+        let bytes        = ua.as_bytes();
+        let mut evidence = vec![];
+
+        for keyword in KEYWORDS.iter() {
+            let found = find_ignore_case(bytes, keyword.as_bytes())
+                .any(|start| is_keyword_at(bytes, start, keyword.len()));
+
+            if found { evidence.push(Evidence::Keyword(keyword)) }
+        }
+        if has_url(bytes)          { evidence.push(Evidence::Url) }
+        if has_email(bytes)        { evidence.push(Evidence::Email) }
+        if !has_mozilla_prefix(ua) { evidence.push(Evidence::NoMozillaPrefix) }
+
+        if evidence.is_empty() { return None }
+
+        let browser_like = ENGINE_TOKENS.iter().any(|token| ua.contains(token));
+        let weight: u8   = evidence.iter().map(|e| e.weight(browser_like)).sum();
+        let confidence = if weight >= 4 {
+            Confidence::High
+        } else if weight >= 2 {
+            Confidence::Medium
+        } else {
+            Confidence::Low
+        };
+
+        Some(HeuristicMatch {
+            confidence: confidence,
+            evidence:   evidence,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use user_agent::UserAgent;
+    use super::{Confidence, Evidence, HeuristicMatch};
+
+    type StaticStr = &'static str;
+
+    const CHROME: StaticStr     = "Mozilla/5.0 (Windows NT 6.1) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/41.0.2228.0 Safari/537.36";
+    const CONTACT: StaticStr    = "Mozilla/5.0 (compatible; +http://example.com/about; ops@example.com)";
+    const SOME_BOT: StaticStr   = "Mozilla/5.0 (compatible; SomeNewBot/0.3; +https://example.com/bot)";
+    const SCRAPER: StaticStr    = "acme-scraper/1.0";
+    const NO_MOZILLA: StaticStr = "abc123";
+    const CUBOT: StaticStr      = "Mozilla/5.0 (Linux; Android 9; CUBOT_X19) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/80.0.3987.99 Mobile Safari/537.36";
+    const ROBOT: StaticStr      = "Mozilla/5.0 (Windows NT 10.0; Win64; x64; Robotics) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36 SpiderBrowser/1.0";
+
+    #[test]
+    fn test_browser_has_no_evidence() {
+        assert_eq!(HeuristicMatch::analyze(CHROME), None)
+    }
+
+    #[test]
+    fn test_keyword_and_url_is_high() {
+        let analyzed = HeuristicMatch::analyze(SOME_BOT).unwrap();
+
+        assert_eq!(analyzed.confidence, Confidence::High);
+        assert_eq!(analyzed.evidence, vec![Evidence::Keyword("bot"), Evidence::Url])
+    }
+
+    #[test]
+    fn test_keyword_without_mozilla() {
+        let analyzed = HeuristicMatch::analyze(SCRAPER).unwrap();
+
+        assert_eq!(analyzed.confidence, Confidence::Medium);
+        assert_eq!(analyzed.evidence, vec![Evidence::Keyword("scraper"), Evidence::NoMozillaPrefix])
+    }
+
+    #[test]
+    fn test_contact_details() {
+        let analyzed = HeuristicMatch::analyze(CONTACT).unwrap();
+
+        assert_eq!(analyzed.confidence, Confidence::High);
+        assert_eq!(analyzed.evidence, vec![Evidence::Url, Evidence::Email])
+    }
+
+    #[test]
+    fn test_only_missing_mozilla_is_low() {
+        let analyzed = HeuristicMatch::analyze(NO_MOZILLA).unwrap();
+
+        assert_eq!(analyzed.confidence, Confidence::Low);
+    }
+
+    #[test]
+    fn test_keywords_need_a_word_boundary() {
+        assert_eq!(HeuristicMatch::analyze(CUBOT), None);
+        assert!(UserAgent::parse(CUBOT).browser.is_some());
+
+        let analyzed = HeuristicMatch::analyze("feedfetcher/2.0").unwrap();
+        assert_eq!(analyzed.evidence, vec![Evidence::Keyword("fetch"), Evidence::NoMozillaPrefix]);
+        assert_eq!(HeuristicMatch::analyze("Mozilla/5.0 (X11; botany)"), None);
+    }
+
+    #[test]
+    fn test_browser_like_needs_more_than_a_keyword() {
+        let analyzed = HeuristicMatch::analyze(ROBOT).unwrap();
+
+        assert_eq!(analyzed.evidence, vec![Evidence::Keyword("spider")]);
+        assert_eq!(analyzed.confidence, Confidence::Low);
+    }
+}
@@ -6,24 +6,79 @@ extern crate regex;
 
 use libc::c_char;
 use std::ffi::{CStr, CString};
+use std::str::FromStr;
+use std::sync::RwLock;
 
-mod bot;
-mod browser;
-mod platform;
-mod user_agent;
+pub mod android_models;
+pub mod apple_devices;
+pub mod app;
+pub mod bot;
+pub mod browser;
+pub mod consistency;
+pub mod device;
+pub mod display;
+pub mod fingerprint;
+pub mod headers;
+pub mod heuristic;
+pub mod library;
+pub mod locale;
+mod phf;
+pub mod platform;
+pub mod precision;
+pub mod threat;
+pub mod tokenizer;
+pub mod traffic;
+pub mod user_agent;
 mod util;
+pub mod verify;
+pub mod version_names;
+pub mod windows;
 
+use app::{AppFormat, AppRegistry};
 use browser::BrowserFamily;
+use consistency::ConsistencyChecker;
+use device::{ClientSignals, FormFactor};
+use platform::PlatformName;
+use traffic::RequestContext;
 use user_agent::UserAgent;
 
+lazy_static! {
+    /// The app formats `parse_user_agent` tries, including any added with `register_app_format`
+    static ref APP_REGISTRY: RwLock<AppRegistry> = RwLock::new(AppRegistry::default());
+}
+
+/// Parse a user agent. Invalid UTF-8 (common in junk and attack payloads) is replaced rather
+/// than rejected.
 #[no_mangle]
 pub extern fn parse_user_agent(cstring: *const c_char) -> *const UserAgent {
-    let string  = unsafe { CStr::from_ptr(cstring) }.to_str().unwrap();
-    let browser = UserAgent::parse(string);
+    let string  = unsafe { CStr::from_ptr(cstring) }.to_string_lossy();
+    let browser = UserAgent::parse_with_apps(&string, &APP_REGISTRY.read().unwrap());
 
     Box::into_raw(Box::new(browser))
 }
 
+/// Add a native app format (see `app::AppFormat`) for every later `parse_user_agent` call to try
+/// before the built-in ones. `os` is a `PlatformName` variant name (eg. "IOS") or null. Returns
+/// false, registering nothing, if the pattern or OS is invalid.
+#[no_mangle]
+pub extern fn register_app_format(id: *const c_char, pattern: *const c_char, os: *const c_char) -> bool {
+    let id      = unsafe { CStr::from_ptr(id) }.to_string_lossy();
+    let pattern = unsafe { CStr::from_ptr(pattern) }.to_string_lossy();
+    let os = if os.is_null() {
+        None
+    } else {
+        match PlatformName::from_str(&unsafe { CStr::from_ptr(os) }.to_string_lossy()) {
+            Ok(os)  => Some(os),
+            Err(()) => return false,
+        }
+    };
+
+    match AppFormat::new(&id, &pattern, os) {
+        Ok(format) => { APP_REGISTRY.write().unwrap().add_format(format); true },
+        Err(_)     => false,
+    }
+}
+
 /// Take back ownership of an externally-owned `Browser` and destructively deallocate it.
 #[no_mangle]
 pub extern fn free_user_agent(ua: *mut UserAgent) {
@@ -49,34 +104,67 @@ is_family!(is_firefox, BrowserFamily::Firefox);
 is_family!(is_opera,   BrowserFamily::Opera);
 is_family!(is_safari,  BrowserFamily::Safari);
 
+/// Whether the device is a phone or tablet. The browser family decides when the form factor is
+/// unknown.
 #[no_mangle]
 pub extern fn is_mobile(ua: *const UserAgent) -> bool {
     let ua = UserAgent::borrow_from_c(ua);
 
-    match ua.browser {
-        Some(ref b) => b.family.is_mobile(),
-        _ => false,
+    match ua.device {
+        Some(ref device) if device.form_factor != FormFactor::Unknown => device.is_mobile(),
+        _ => ua.browser.as_ref().is_some_and(|b| b.family.is_mobile()),
     }
 }
 
 #[no_mangle]
 pub extern fn is_bot(ua: *const UserAgent) -> bool {
+    UserAgent::borrow_from_c(ua).bot.is_some()
+}
+
+#[no_mangle]
+pub extern fn is_ai_crawler(ua: *const UserAgent) -> bool {
+    let ua = UserAgent::borrow_from_c(ua);
+
+    match ua.bot {
+        Some(ref bot) => bot.is_ai_crawler(),
+        _ => false,
+    }
+}
+
+#[no_mangle]
+pub extern fn is_link_preview(ua: *const UserAgent) -> bool {
+    let ua = UserAgent::borrow_from_c(ua);
+
+    match ua.bot {
+        Some(ref bot) => bot.is_link_preview(),
+        _ => false,
+    }
+}
+
+/// Whether the bot was flagged by the heuristics rather than found in the bot catalog
+#[no_mangle]
+pub extern fn is_heuristic_bot(ua: *const UserAgent) -> bool {
     let ua = UserAgent::borrow_from_c(ua);
 
     match ua.bot {
-        Some(_) => true,
+        Some(ref bot) => bot.is_heuristic(),
         _ => false,
     }
 }
 
+/// Versions above 127 don't fit the `i8` these have always returned, so they're capped there
 #[no_mangle]
 pub extern fn get_browser_major_version(ua: *const UserAgent) -> i8 {
-    UserAgent::borrow_from_c(ua).browser.clone().map_or(0, |b| b.major_version)
+    UserAgent::borrow_from_c(ua).browser.clone().map_or(0, |b| saturate(b.major_version))
 }
 
 #[no_mangle]
 pub extern fn get_browser_minor_version(ua: *const UserAgent) -> i8 {
-    UserAgent::borrow_from_c(ua).browser.clone().map_or(0, |b| b.minor_version)
+    UserAgent::borrow_from_c(ua).browser.clone().map_or(0, |b| saturate(b.minor_version))
+}
+
+fn saturate(version: i16) -> i8 {
+    version.clamp(i8::MIN as i16, i8::MAX as i16) as i8
 }
 
 /// Returns the user agent's browser family name as a heap-allocated `CString`
@@ -96,10 +184,262 @@ pub extern fn get_bot_name(ua: *const UserAgent) -> *mut c_char {
     CString::new(name).unwrap().into_raw()
 }
 
+#[no_mangle]
+pub extern fn is_library(ua: *const UserAgent) -> bool {
+    UserAgent::borrow_from_c(ua).library.is_some()
+}
+
+/// Returns the native app's name (eg. "MyApp" from `MyApp/1.0 CFNetwork/...`), or "Other" if
+/// there's no app or it doesn't name itself, as a `CString` (must free later)
+#[no_mangle]
+pub extern fn get_app_name(ua: *const UserAgent) -> *mut c_char {
+    let name = UserAgent::borrow_from_c(ua).app.as_ref()
+        .and_then(|app| app.name.clone())
+        .unwrap_or_else(|| "Other".to_owned());
+
+    CString::new(name).unwrap().into_raw()
+}
+
+/// Returns the native app's version as sent, or an empty `CString` (must free later)
+#[no_mangle]
+pub extern fn get_app_version(ua: *const UserAgent) -> *mut c_char {
+    let version = UserAgent::borrow_from_c(ua).app.as_ref()
+        .and_then(|app| app.version.clone())
+        .unwrap_or_default();
+
+    CString::new(version).unwrap().into_raw()
+}
+
+/// Returns the device model the native app reports (eg. "Pixel 5") as a `CString` (must free
+/// later)
+#[no_mangle]
+pub extern fn get_app_model(ua: *const UserAgent) -> *mut c_char {
+    let model = UserAgent::borrow_from_c(ua).app.as_ref()
+        .and_then(|app| app.model.clone())
+        .unwrap_or_else(|| "Other".to_owned());
+
+    CString::new(model).unwrap().into_raw()
+}
+
+/// Returns the screen scale the native app reports, or 0.0 if it doesn't
+#[no_mangle]
+pub extern fn get_app_scale(ua: *const UserAgent) -> f32 {
+    UserAgent::borrow_from_c(ua).app.as_ref().and_then(|app| app.scale).unwrap_or(0.0)
+}
+
+#[no_mangle]
+pub extern fn get_library_name(ua: *const UserAgent) -> *mut c_char {
+    let name = UserAgent::borrow_from_c(ua).library.clone()
+        .map_or("Other".to_owned(), |library| library.to_string());
+
+    CString::new(name).unwrap().into_raw()
+}
+
+#[no_mangle]
+pub extern fn get_library_major_version(ua: *const UserAgent) -> i8 {
+    UserAgent::borrow_from_c(ua).library.clone().map_or(0, |l| l.major_version)
+}
+
+#[no_mangle]
+pub extern fn get_library_minor_version(ua: *const UserAgent) -> i8 {
+    UserAgent::borrow_from_c(ua).library.clone().map_or(0, |l| l.minor_version)
+}
+
+/// Returns the bot's category (eg. "Search", "Monitor") as a `CString` (must free later)
+#[no_mangle]
+pub extern fn get_bot_category(ua: *const UserAgent) -> *mut c_char {
+    let category = UserAgent::borrow_from_c(ua).bot.clone()
+        .map_or("Other".to_owned(), |bot| bot.name.category().to_string());
+
+    CString::new(category).unwrap().into_raw()
+}
+
+/// Chance (0.0 to 1.0) that the user agent is spoofed, based on `ConsistencyChecker`'s default
+/// rules
+#[no_mangle]
+pub extern fn get_spoof_likelihood(ua: *const UserAgent) -> f32 {
+    let ua = UserAgent::borrow_from_c(ua);
+
+    ConsistencyChecker::default().check(ua).spoof_likelihood
+}
+
+/// Whether the user agent contains a known attack payload (see `threat::scan`)
+#[no_mangle]
+pub extern fn is_threat(ua: *const UserAgent) -> bool {
+    !threat::scan(&UserAgent::borrow_from_c(ua).source).is_empty()
+}
+
+/// Returns the kinds of threat found as a comma-separated `CString` (must free later)
+#[no_mangle]
+pub extern fn get_threats(ua: *const UserAgent) -> *mut c_char {
+    let findings = threat::scan(&UserAgent::borrow_from_c(ua).source);
+    let kinds: Vec<String> = threat::kinds(&findings).iter().map(|k| k.to_string()).collect();
+
+    CString::new(kinds.join(",")).unwrap().into_raw()
+}
+
+/// Returns the platform's name and version (eg. "OS X Mavericks", "Android KitKat (API 19)") as
+/// a `CString` (must free later)
+#[no_mangle]
+pub extern fn get_platform_display_name(ua: *const UserAgent) -> *mut c_char {
+    let name = UserAgent::borrow_from_c(ua).platform.as_ref()
+        .map_or("Other".to_owned(), |platform| platform.display_name());
+
+    CString::new(name).unwrap().into_raw()
+}
+
+/// Returns the platform's CPU architecture (eg. "x64", "ARM64") as a `CString` (must free later)
+#[no_mangle]
+pub extern fn get_platform_architecture(ua: *const UserAgent) -> *mut c_char {
+    let architecture = UserAgent::borrow_from_c(ua).platform.as_ref()
+        .and_then(|platform| platform.architecture)
+        .map_or("Other".to_owned(), |architecture| architecture.to_string());
+
+    CString::new(architecture).unwrap().into_raw()
+}
+
+/// Returns 32 or 64, or 0 if the bitness is unknown
+#[no_mangle]
+pub extern fn get_platform_bitness(ua: *const UserAgent) -> u8 {
+    UserAgent::borrow_from_c(ua).platform.as_ref()
+        .and_then(|platform| platform.bitness)
+        .unwrap_or(0)
+}
+
+/// Apply the `Sec-CH-UA-Arch` and `Sec-CH-UA-Bitness` client hints to the parsed platform.
+/// Either string may be null if the hint wasn't sent.
+#[no_mangle]
+pub extern fn apply_architecture_hints(ua: *mut UserAgent, arch: *const c_char, bitness: *const c_char) {
+    let ua = unsafe { &mut *ua };
+    let hint = |cstring: *const c_char| if cstring.is_null() {
+        None
+    } else {
+        Some(unsafe { CStr::from_ptr(cstring) }.to_string_lossy().into_owned())
+    };
+
+    let (arch, bitness) = (hint(arch), hint(bitness));
+    if let Some(ref mut platform) = ua.platform {
+        platform.apply_client_hints(arch.as_deref(), bitness.as_deref())
+    }
+}
+
+/// Returns the device's brand and marketing name (eg. "Samsung Galaxy S21") as a `CString`
+/// (must free later)
+#[no_mangle]
+pub extern fn get_device_name(ua: *const UserAgent) -> *mut c_char {
+    let name = UserAgent::borrow_from_c(ua).device.as_ref()
+        .and_then(|device| device.model_info)
+        .map_or("Other".to_owned(), |info| info.full_name());
+
+    CString::new(name).unwrap().into_raw()
+}
+
+/// Returns the device's brand (eg. "Samsung") as a `CString` (must free later)
+#[no_mangle]
+pub extern fn get_device_brand(ua: *const UserAgent) -> *mut c_char {
+    let brand = UserAgent::borrow_from_c(ua).device.as_ref()
+        .and_then(|device| device.model_info)
+        .map_or("Other", |info| info.brand);
+
+    CString::new(brand).unwrap().into_raw()
+}
+
+/// Returns the device's chip (eg. "A13 Bionic") as a `CString` (must free later). Empty if
+/// it's unknown.
+#[no_mangle]
+pub extern fn get_device_chip(ua: *const UserAgent) -> *mut c_char {
+    let chip = UserAgent::borrow_from_c(ua).device.as_ref()
+        .and_then(|device| device.model_info)
+        .and_then(|info| info.chip)
+        .unwrap_or("");
+
+    CString::new(chip).unwrap().into_raw()
+}
+
+/// Returns the screen scale (device pixels per CSS pixel), or 0.0 if it's unknown
+#[no_mangle]
+pub extern fn get_display_scale(ua: *const UserAgent) -> f32 {
+    UserAgent::borrow_from_c(ua).display.as_ref().and_then(|display| display.scale).unwrap_or(0.0)
+}
+
+/// Returns the viewport width in CSS pixels, or 0 if it's unknown
+#[no_mangle]
+pub extern fn get_display_viewport_width(ua: *const UserAgent) -> u32 {
+    UserAgent::borrow_from_c(ua).display.as_ref().and_then(|display| display.viewport_width).unwrap_or(0)
+}
+
+/// Returns the width the page wants an image at in device pixels (`Sec-CH-Width`), or 0 if it's
+/// unknown
+#[no_mangle]
+pub extern fn get_display_width(ua: *const UserAgent) -> u32 {
+    UserAgent::borrow_from_c(ua).display.as_ref().and_then(|display| display.width).unwrap_or(0)
+}
+
+/// Apply a client hint header (eg. `Sec-CH-DPR`) to the parsed user agent; see
+/// `ClientSignals::apply_client_hint`
+#[no_mangle]
+pub extern fn apply_client_hint(ua: *mut UserAgent, name: *const c_char, value: *const c_char) {
+    if name.is_null() || value.is_null() { return }
+
+    let ua    = unsafe { &mut *ua };
+    let name  = unsafe { CStr::from_ptr(name) }.to_string_lossy();
+    let value = unsafe { CStr::from_ptr(value) }.to_string_lossy();
+
+    let mut signals = ClientSignals::default();
+    signals.apply_client_hint(&name, &value);
+    ua.apply_client_signals(&signals)
+}
+
+/// Apply `navigator.maxTouchPoints`, as collected by the page, to the parsed user agent. A
+/// touchscreen "Mac" is an iPad in desktop mode.
+#[no_mangle]
+pub extern fn apply_max_touch_points(ua: *mut UserAgent, max_touch_points: u32) {
+    let ua      = unsafe { &mut *ua };
+    let signals = ClientSignals { max_touch_points: Some(max_touch_points), ..ClientSignals::default() };
+
+    ua.apply_client_signals(&signals)
+}
+
+/// Returns the best-guess locale (eg. "ko-KR") from `locale::best_locale` as a `CString`, or an
+/// empty one if there's none (must free later). `accept_language` may be null if the header
+/// wasn't sent.
+#[no_mangle]
+pub extern fn get_locale(ua: *const UserAgent, accept_language: *const c_char) -> *mut c_char {
+    let ua = UserAgent::borrow_from_c(ua);
+    let accept_language = if accept_language.is_null() {
+        None
+    } else {
+        Some(unsafe { CStr::from_ptr(accept_language) }.to_string_lossy().into_owned())
+    };
+
+    let locale = locale::best_locale(ua, accept_language.as_deref())
+        .map_or(String::new(), |locale| locale.to_string());
+
+    CString::new(locale).unwrap().into_raw()
+}
+
+/// Returns the `traffic::TrafficKind` (eg. "Human", "Automation") from `classify_traffic` as a
+/// `CString` (must free later)
+#[no_mangle]
+pub extern fn get_traffic_verdict(ua: *const UserAgent) -> *mut c_char {
+    let ua      = UserAgent::borrow_from_c(ua);
+    let verdict = traffic::classify_traffic(ua, &RequestContext::default());
+
+    CString::new(verdict.kind.to_string()).unwrap().into_raw()
+}
+
+/// How likely (0.0 to 1.0) the request is automated, based on the user agent alone
+#[no_mangle]
+pub extern fn get_traffic_score(ua: *const UserAgent) -> f32 {
+    let ua = UserAgent::borrow_from_c(ua);
+
+    traffic::classify_traffic(ua, &RequestContext::default()).score
+}
+
 /// Returns the original user agent that was parsed as a `CString` (must free later)
 #[no_mangle]
 pub extern fn get_user_agent(ua: *const UserAgent) -> *mut c_char {
-    let ref ua = UserAgent::borrow_from_c(ua);
+    let ua = UserAgent::borrow_from_c(ua);
 
     CString::new(ua.source.clone()).unwrap().into_raw()
 }
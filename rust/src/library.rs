@@ -0,0 +1,169 @@
+use regex::{Captures, Regex};
+use std::fmt;
+use std::str::FromStr;
+
+/// HTTP client libraries and command-line tools. These are kept apart from `Bot` since they're
+/// as likely to be an API client (eg. one of our own SDKs) as they are a scraper.
+#[derive(Clone, Debug, PartialEq)]
+pub enum LibraryName {
+    Aiohttp,
+    ApacheHttpClient,
+    Axios,
+    Curl,
+    GoHttpClient, // Go language's HTTP package
+    Guzzle,
+    Java, // Java's built-in `HttpURLConnection`
+    LibwwwPerl,
+    NodeFetch,
+    Okhttp,
+    PythonRequests,
+    PythonUrllib,
+    Reqwest,
+    Wget,
+}
+
+impl fmt::Display for LibraryName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::LibraryName::*;
+
+        let name = match *self {
+            Aiohttp          => "aiohttp",
+            ApacheHttpClient => "Apache-HttpClient",
+            Axios            => "axios",
+            Curl             => "curl",
+            GoHttpClient     => "Go-http-client",
+            Guzzle           => "Guzzle",
+            Java             => "Java",
+            LibwwwPerl       => "libwww-perl",
+            NodeFetch        => "node-fetch",
+            Okhttp           => "okhttp",
+            PythonRequests   => "python-requests",
+            PythonUrllib     => "Python-urllib",
+            Reqwest          => "reqwest",
+            Wget             => "Wget",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Library {
+    pub name: LibraryName,
+    pub major_version: i8,
+    pub minor_version: i8,
+}
+
+type Matcher = (LibraryName, Regex);
+
+lazy_static! {
+    // NOTE: Order of tests is significant
+    static ref MATCH_SEQUENCE: Vec<Matcher> = {
+        use self::LibraryName::*;
+
+        let matcher = |name: LibraryName, product: &str| {
+            // The product has to start a token (so `curl` doesn't match `pycurl/7.43`) unless
+            // it's anchored already. The version is optional, but the product has to end there
+            // if it's missing.
+            let start = if product.starts_with('^') { "" } else { r"(?:^|\s)" };
+            let regex = format!(r"{}{}(?:/(\d+)(?:\.(\d+))?|\s|$)", start, product);
+            (name, Regex::new(&regex).unwrap())
+        };
+
+        vec![
+            matcher(ApacheHttpClient, "Apache-HttpClient"), // Includes a `Java/x.y` comment
+            matcher(Aiohttp,          "aiohttp"),
+            matcher(Axios,            "axios"),
+            matcher(Curl,             "curl"),
+            matcher(GoHttpClient,     "Go-http-client"),
+            matcher(Guzzle,           "GuzzleHttp"),
+            matcher(LibwwwPerl,       "libwww-perl"),
+            matcher(NodeFetch,        "node-fetch"),
+            matcher(Okhttp,           "okhttp"),
+            matcher(PythonRequests,   "python-requests"),
+            matcher(PythonUrllib,     "Python-urllib"),
+            matcher(Reqwest,          "reqwest"),
+            matcher(Wget,             "Wget"),
+            matcher(Java,             "^Java"),
+        ]
+    };
+}
+
+impl Library {
+    pub fn new(name: LibraryName, versions: (i8, i8)) -> Library {
+        Library {
+            name:          name,
+            major_version: versions.0,
+            minor_version: versions.1,
+        }
+    }
+
+    pub fn parse(ua: &str) -> Option<Library> {
+        for (name, regex) in MATCH_SEQUENCE.iter() {
+            if let Some(captures) = regex.captures(ua) {
+                return Some(Library::new(name.clone(), Library::versions(&captures)))
+            }
+        }
+
+        None
+    }
+
+    /// Versions are optional (eg. `node-fetch` 3 doesn't send one), so missing or unparseable
+    /// versions become zero.
+    fn versions(captures: &Captures) -> (i8, i8) {
+        let version = |index| captures.at(index).and_then(|v| i8::from_str(v).ok()).unwrap_or(0);
+
+        (version(1), version(2))
+    }
+}
+
+impl fmt::Display for Library {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.name.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Library, LibraryName};
+
+    type StaticStr = &'static str;
+
+    const APACHE: StaticStr   = "Apache-HttpClient/4.5.13 (Java/1.8.0_292)";
+    const CURL: StaticStr     = "curl/7.68.0";
+    const GO: StaticStr       = "Go-http-client/1.1";
+    const GUZZLE: StaticStr   = "GuzzleHttp/7";
+    const JAVA: StaticStr     = "Java/1.8.0_151";
+    const REQUESTS: StaticStr = "python-requests/2.25.1";
+    const CHROME: StaticStr   = "Mozilla/5.0 (Windows NT 6.1) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/41.0.2228.0 Safari/537.36";
+
+    #[test]
+    fn test_parse_libraries() {
+        assert_eq!(Library::parse(CURL), Some(Library::new(LibraryName::Curl, (7, 68))));
+        assert_eq!(Library::parse(GO), Some(Library::new(LibraryName::GoHttpClient, (1, 1))));
+        assert_eq!(Library::parse(REQUESTS), Some(Library::new(LibraryName::PythonRequests, (2, 25))));
+        assert_eq!(Library::parse(CHROME), None);
+    }
+
+    #[test]
+    fn test_parse_without_minor_version() {
+        assert_eq!(Library::parse(GUZZLE), Some(Library::new(LibraryName::Guzzle, (7, 0))));
+    }
+
+    #[test]
+    fn test_parse_java_precedence() {
+        assert_eq!(Library::parse(APACHE), Some(Library::new(LibraryName::ApacheHttpClient, (4, 5))));
+        assert_eq!(Library::parse(JAVA), Some(Library::new(LibraryName::Java, (1, 8))));
+    }
+
+    #[test]
+    fn test_products_start_a_token() {
+        assert_eq!(Library::parse("PycURL/7.43.0 libcurl/7.64.0"), None);
+        assert_eq!(Library::parse("MyClient/1.0 (based on axios)"), None);
+        assert_eq!(Library::parse("MyClient/1.0 axios/1.6.2"), Some(Library::new(LibraryName::Axios, (1, 6))));
+    }
+
+    #[test]
+    fn test_to_string() {
+        assert_eq!(LibraryName::PythonRequests.to_string(), "python-requests".to_owned());
+    }
+}
@@ -0,0 +1,195 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use tokenizer::{tokenize, Comment};
+use user_agent::UserAgent;
+
+/// A BCP 47 language tag, cut down to its language, script and region subtags and normalized
+/// to their usual case (`zh-Hant-TW`, `pt-BR`, `ko`)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Locale {
+    pub language: String,
+    pub script: Option<String>,
+    pub region: Option<String>,
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.language)?;
+        if let Some(ref script) = self.script { write!(f, "-{}", script)? }
+        if let Some(ref region) = self.region { write!(f, "-{}", region)? }
+
+        Ok(())
+    }
+}
+
+fn is_alpha(subtag: &str, lengths: &[usize]) -> bool {
+    lengths.contains(&subtag.len()) && subtag.bytes().all(|b| b.is_ascii_alphabetic())
+}
+
+fn is_region(subtag: &str) -> bool {
+    is_alpha(subtag, &[2]) || (subtag.len() == 3 && subtag.bytes().all(|b| b.is_ascii_digit()))
+}
+
+impl Locale {
+    /// Parse a language tag, accepting `_` as well as `-`. Variants and extensions are dropped.
+    pub fn parse(tag: &str) -> Option<Locale> {
+        let mut subtags = tag.trim().split(['-', '_']).peekable();
+
+        let language = subtags.next().filter(|l| is_alpha(l, &[2, 3]))?.to_lowercase();
+
+        let script = subtags.peek().cloned().filter(|s| is_alpha(s, &[4])).map(|script| {
+            subtags.next();
+            script[..1].to_uppercase() + &script[1..].to_lowercase()
+        });
+        let region = subtags.peek().cloned().filter(|r| is_region(r)).map(str::to_uppercase);
+
+        Some(Locale {
+            language: language,
+            script:   script,
+            region:   region,
+        })
+    }
+
+    /// Older browsers put the locale in a comment of its own: `(Linux; U; Android 4.0.3; ko-kr;
+    /// ...)` or `(Windows NT 6.1; U; pt)`. Only a two-letter language and an optional region are
+    /// accepted there, since other short comment items (`iOS`, `ARM`, `x64`) aren't locales;
+    /// nor is Android WebView's `wv`.
+    pub fn from_user_agent(ua: &str) -> Option<Locale> {
+        tokenize(ua).comments().filter_map(|comment| Locale::from_comment(&comment)).next()
+    }
+
+    fn from_comment(comment: &Comment) -> Option<Locale> {
+        for item in comment.items() {
+            let mut subtags = item.text.split(['-', '_']);
+            let language    = subtags.next().filter(|l| is_alpha(l, &[2]) && *l != "wv");
+            let region      = subtags.next();
+
+            if language.is_some() && region.is_none_or(|r| is_alpha(r, &[2])) && subtags.next().is_none() {
+                return Locale::parse(item.text)
+            }
+
+            // Opera Mini nests the rest of its user agent in a comment
+            if item.text.contains('(') {
+                if let Some(locale) = tokenize(item.text).comments().filter_map(|c| Locale::from_comment(&c)).next() {
+                    return Some(locale)
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// An `Accept-Language` entry: a language tag or `*`, and its quality from 0.0 to 1.0
+#[derive(Clone, Debug, PartialEq)]
+pub struct LanguageRange {
+    pub range: String,
+    pub quality: f32,
+}
+
+/// Parse an `Accept-Language` header into its ranges, best first. Ranges with the same quality
+/// keep their order, and malformed entries are skipped.
+pub fn parse_accept_language(header: &str) -> Vec<LanguageRange> {
+    let mut ranges: Vec<LanguageRange> = header.split(',').filter_map(|entry| {
+        let mut parts = entry.split(';');
+        let range     = parts.next()?.trim();
+        if range.is_empty() { return None }
+
+        let quality = match parts.next().map(str::trim) {
+            Some(param) if param.starts_with("q=") => f32::from_str(&param[2..]).ok()?,
+            Some(_) => return None,
+            None    => 1.0,
+        };
+
+        Some(LanguageRange {
+            range:   range.to_owned(),
+            quality: quality.clamp(0.0, 1.0),
+        })
+    }).collect();
+
+    ranges.sort_by(|a, b| b.quality.partial_cmp(&a.quality).unwrap_or(Ordering::Equal));
+    ranges
+}
+
+/// The best guess at the user's locale. `Accept-Language` reflects the current browser setting,
+/// so its first acceptable range wins; the user agent's locale fills in a missing region when
+/// the languages agree, and is the fallback when there's no header.
+pub fn best_locale(ua: &UserAgent, accept_language: Option<&str>) -> Option<Locale> {
+    let preferred = accept_language
+        .map(parse_accept_language)
+        .and_then(|ranges| {
+            ranges.iter()
+                .filter(|range| range.quality > 0.0)
+                .filter_map(|range| Locale::parse(&range.range))
+                .next()
+        });
+
+    match (preferred, ua.locale.clone()) {
+        (Some(ref preferred), Some(ref from_ua))
+            if preferred.region.is_none() && preferred.language == from_ua.language => Some(from_ua.clone()),
+        (Some(preferred), _) => Some(preferred),
+        (None, from_ua)      => from_ua,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use user_agent::UserAgent;
+    use super::{best_locale, parse_accept_language, Locale};
+
+    type StaticStr = &'static str;
+
+    const ANDROID_4: StaticStr  = "Mozilla/5.0 (Linux; U; Android 4.0.3; ko-kr; LG-L160L Build/IML74K) AppleWebkit/534.30 (KHTML, like Gecko) Version/4.0 Mobile Safari/534.30";
+    const OPERA_11: StaticStr   = "Opera/9.80 (Windows NT 6.1; WOW64; U; pt) Presto/2.10.229 Version/11.62";
+    const OPERA_MINI: StaticStr = "Opera/9.80 (J2ME/MIDP; Opera Mini/9.80 (S60; SymbOS; Opera Mobi/23.348; U; en) Presto/2.5.25) Version/10.54";
+    const SAFARI_5: StaticStr   = "Mozilla/5.0 (Macintosh; U; Intel Mac OS X 10_6_3; en-us) AppleWebKit/534.1+ (KHTML, like Gecko) Version/5.0 Safari/533.16";
+    const IPHONE: StaticStr     = "Mozilla/5.0 (iPhone; CPU iPhone OS 17_0 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.0 Mobile/15E148 Safari/604.1";
+    const WEBVIEW: StaticStr    = "Mozilla/5.0 (Linux; Android 10; K; wv) AppleWebKit/537.36 (KHTML, like Gecko) Version/4.0 Chrome/120.0.0.0 Mobile Safari/537.36";
+
+    fn locale(ua: &str) -> Option<String> {
+        UserAgent::parse(ua).locale.map(|locale| locale.to_string())
+    }
+
+    #[test]
+    fn test_locale_from_user_agent() {
+        assert_eq!(locale(ANDROID_4), Some("ko-KR".to_owned()));
+        assert_eq!(locale(OPERA_11), Some("pt".to_owned()));
+        assert_eq!(locale(OPERA_MINI), Some("en".to_owned()));
+        assert_eq!(locale(SAFARI_5), Some("en-US".to_owned()));
+        assert_eq!(locale(IPHONE), None);
+        assert_eq!(locale(WEBVIEW), None);
+    }
+
+    #[test]
+    fn test_parse_tags() {
+        assert_eq!(Locale::parse("zh_hant_tw").unwrap().to_string(), "zh-Hant-TW");
+        assert_eq!(Locale::parse("es-419").unwrap().region, Some("419".to_owned()));
+        assert_eq!(Locale::parse("de-CH-1996").unwrap().to_string(), "de-CH");
+        assert_eq!(Locale::parse("*"), None);
+        assert_eq!(Locale::parse("english"), None);
+    }
+
+    #[test]
+    fn test_parse_accept_language() {
+        let ranges = parse_accept_language("fr-CH, fr;q=0.9, en;q=0.8, de;q=0.7, *;q=0.5, bad;x=1");
+        let ranges: Vec<(&str, f32)> = ranges.iter().map(|r| (r.range.as_str(), r.quality)).collect();
+
+        assert_eq!(ranges, [("fr-CH", 1.0), ("fr", 0.9), ("en", 0.8), ("de", 0.7), ("*", 0.5)]);
+        assert_eq!(parse_accept_language("de;q=0.5, en")[0].range, "en");
+        assert!(parse_accept_language("").is_empty());
+    }
+
+    #[test]
+    fn test_best_locale() {
+        let android = UserAgent::parse(ANDROID_4);
+        let best    = |header| best_locale(&android, header).map(|locale| locale.to_string());
+
+        assert_eq!(best(None), Some("ko-KR".to_owned()));
+        assert_eq!(best(Some("ko, en;q=0.5")), Some("ko-KR".to_owned()));
+        assert_eq!(best(Some("*;q=0.5, en-GB;q=0.8")), Some("en-GB".to_owned()));
+        assert_eq!(best(Some("en;q=0")), Some("ko-KR".to_owned()));
+        assert_eq!(best_locale(&UserAgent::parse(IPHONE), Some("*")), None);
+    }
+}
@@ -0,0 +1,81 @@
+//! Minimal perfect hashing ("hash, displace and compress") for the tables `build.rs` generates.
+//! `build.rs` includes this file too, so both sides hash keys the same way.
+//!
+//! Keys hash into buckets, and each bucket gets a seed under which all of its keys land in free
+//! slots, so a lookup is two hashes and one comparison. Hashing ignores ASCII case.
+
+use std::cmp::Reverse;
+
+fn hash(key: &str, seed: u32) -> u32 {
+    // FNV-1a, then MurmurHash3's finalizer so that nearby seeds give unrelated hashes
+    let mut hash = 0x811c_9dc5 ^ seed;
+    for byte in key.bytes() {
+        hash ^= u32::from(byte.to_ascii_uppercase());
+        hash  = hash.wrapping_mul(0x0100_0193);
+    }
+
+    hash ^= hash >> 16;
+    hash  = hash.wrapping_mul(0x85eb_ca6b);
+    hash ^= hash >> 13;
+    hash  = hash.wrapping_mul(0xc2b2_ae35);
+    hash ^ (hash >> 16)
+}
+
+/// The slot `key` would occupy in a table of `len` entries. The caller still has to compare the
+/// key in that slot, since keys that aren't in the table land somewhere too.
+#[allow(dead_code)] // Only used by the library
+pub fn slot(key: &str, seeds: &[u32], len: usize) -> usize {
+    let bucket = hash(key, 0) as usize % seeds.len();
+
+    hash(key, seeds[bucket]) as usize % len
+}
+
+/// Returns the seed for each bucket and, for each slot, the index of the key in it. Keys must be
+/// unique (ignoring ASCII case) and there must be at least one.
+#[allow(dead_code)] // Only used by `build.rs`
+pub fn generate(keys: &[String]) -> (Vec<u32>, Vec<usize>) {
+    let len         = keys.len();
+    let bucket_size = 2;
+    let mut buckets: Vec<Vec<usize>> = vec![vec![]; len.div_ceil(bucket_size)];
+
+    for (index, key) in keys.iter().enumerate() {
+        let bucket = hash(key, 0) as usize % buckets.len();
+        buckets[bucket].push(index)
+    }
+
+    // Place the biggest buckets first, while there's the most room
+    let mut order: Vec<usize> = (0..buckets.len()).collect();
+    order.sort_by_key(|&bucket| Reverse(buckets[bucket].len()));
+
+    let mut seeds = vec![0; buckets.len()];
+    let mut slots: Vec<Option<usize>> = vec![None; len];
+
+    for bucket in order {
+        let keys_in_bucket = &buckets[bucket];
+        if keys_in_bucket.is_empty() { continue }
+
+        let mut seed = 1;
+        loop {
+            let mut candidate: Vec<usize> = keys_in_bucket.iter()
+                .map(|&index| hash(&keys[index], seed) as usize % len)
+                .collect();
+
+            let free = candidate.iter().all(|&slot| slots[slot].is_none());
+            candidate.sort_unstable();
+            candidate.dedup();
+
+            if free && candidate.len() == keys_in_bucket.len() {
+                for &index in keys_in_bucket {
+                    slots[hash(&keys[index], seed) as usize % len] = Some(index)
+                }
+                seeds[bucket] = seed;
+                break
+            }
+
+            seed += 1;
+            assert!(seed < 10_000_000, "no seed places bucket {} (duplicate keys?)", bucket);
+        }
+    }
+
+    (seeds, slots.into_iter().map(|slot| slot.unwrap()).collect())
+}
@@ -1,35 +1,363 @@
 use regex::{Error as RegexError, Regex};
+use std::fmt;
 use std::str::FromStr;
 
+use apple_devices;
+use precision::Precision;
+use tokenizer::tokenize;
+use version_names::{self, VersionName};
+use windows::{WindowsRelease, WindowsVersion};
+
 use self::MatchPattern::*;
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum PlatformName {
     Android,
+    BlackBerry, // BlackBerry OS, BlackBerry Tablet OS and BlackBerry 10
+    ChromeOS,
+    FirefoxOS,
+    FreeBSD,
+    HarmonyOS,
     IOS,
+    KaiOS,
     Linux,
     Mac,
+    NetBSD,
+    OpenBSD,
+    Sailfish,
+    Solaris,
+    Symbian,
+    Tizen,
+    WebOS,
     Windows,
+    WindowsPhone, // Includes Windows 10 Mobile
+}
+
+impl PlatformName {
+    pub fn is_bsd(&self) -> bool {
+        use self::PlatformName::*;
+
+        matches!(*self, FreeBSD | NetBSD | OpenBSD)
+    }
+}
+
+impl FromStr for PlatformName {
+    type Err = ();
+
+    /// Parses the variant name (`"WindowsPhone"`)
+    fn from_str(string: &str) -> Result<PlatformName, ()> {
+        use self::PlatformName::*;
+
+        match string {
+            "Android"      => Ok(Android),
+            "BlackBerry"   => Ok(BlackBerry),
+            "ChromeOS"     => Ok(ChromeOS),
+            "FirefoxOS"    => Ok(FirefoxOS),
+            "FreeBSD"      => Ok(FreeBSD),
+            "HarmonyOS"    => Ok(HarmonyOS),
+            "IOS"          => Ok(IOS),
+            "KaiOS"        => Ok(KaiOS),
+            "Linux"        => Ok(Linux),
+            "Mac"          => Ok(Mac),
+            "NetBSD"       => Ok(NetBSD),
+            "OpenBSD"      => Ok(OpenBSD),
+            "Sailfish"     => Ok(Sailfish),
+            "Solaris"      => Ok(Solaris),
+            "Symbian"      => Ok(Symbian),
+            "Tizen"        => Ok(Tizen),
+            "WebOS"        => Ok(WebOS),
+            "Windows"      => Ok(Windows),
+            "WindowsPhone" => Ok(WindowsPhone),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for PlatformName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::PlatformName::*;
+
+        let name = match *self {
+            FirefoxOS    => "Firefox OS",
+            IOS          => "iOS",
+            Mac          => "macOS",
+            Sailfish     => "Sailfish OS",
+            WebOS        => "webOS",
+            WindowsPhone => "Windows Phone",
+            _ => return fmt::Debug::fmt(self, f),
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DistributionName {
+    Arch,
+    CentOS,
+    Debian,
+    Fedora,
+    Mint,
+    Ubuntu,
+}
+
+/// A Linux distribution. Distributions mostly don't identify themselves, so this is only
+/// present when a browser packaged by the distribution adds its own token (eg.
+/// `Ubuntu/14.10`). Missing versions are zero.
+#[derive(Debug, PartialEq)]
+pub struct Distribution {
+    pub name: DistributionName,
+    pub major_version: i16,
+    pub minor_version: i16,
+}
+
+lazy_static! {
+    static ref DISTRIBUTION_REGEX: Regex = Regex::new(r"(?P<name>Ubuntu|Fedora|Debian|Linux Mint|CentOS|Arch Linux)(?:/(?P<major>\d+)(?:\.(?P<minor>\d+))?)?").unwrap();
+    // Fedora's Firefox packages send their own version (`Fedora/3.6.3-4.fc13`); the release is
+    // in the suffix
+    static ref FEDORA_RELEASE_REGEX: Regex = Regex::new(r"\.fc(\d+)").unwrap();
+}
+
+impl Distribution {
+    pub fn new(name: DistributionName, major_version: i16, minor_version: i16) -> Distribution {
+        Distribution {
+            name:          name,
+            major_version: major_version,
+            minor_version: minor_version,
+        }
+    }
+
+    pub fn parse(ua: &str) -> Option<Distribution> {
+        use self::DistributionName::*;
+
+        let captures = DISTRIBUTION_REGEX.captures(ua)?;
+        let version  = |name| captures.name(name).and_then(|v| i16::from_str(v).ok()).unwrap_or(0);
+
+        let name = match captures.name("name") {
+            Some("Ubuntu")     => Ubuntu,
+            Some("Fedora")     => Fedora,
+            Some("Debian")     => Debian,
+            Some("Linux Mint") => Mint,
+            Some("CentOS")     => CentOS,
+            _                  => Arch,
+        };
+
+        if name == Fedora {
+            let release = FEDORA_RELEASE_REGEX.captures(ua)
+                .and_then(|captures| captures.at(1).and_then(|v| i16::from_str(v).ok()))
+                .unwrap_or(0);
+            return Some(Distribution::new(Fedora, release, 0))
+        }
+
+        Some(Distribution::new(name, version("major"), version("minor")))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Architecture {
+    Arm,
+    Arm64,
+    PowerPC,
+    X64, // Also known as x86-64 and AMD64
+    X86,
+}
+
+impl fmt::Display for Architecture {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::Architecture::*;
+
+        let name = match *self {
+            Arm     => "ARM",
+            Arm64   => "ARM64",
+            PowerPC => "PowerPC",
+            X64     => "x64",
+            X86     => "x86",
+        };
+        f.write_str(name)
+    }
+}
+
+lazy_static! {
+    // NOTE: Order of alternatives is significant; `x86_64` has to be tried before `x86`
+    static ref ARCHITECTURE_REGEX: Regex = Regex::new(r"(?i)\b(x86_64|amd64|x64|win64|wow64|intel mac os x|aarch64|arm64|armv\d+l?|arm|i[3-6]86|x86|ppc64|ppc|powerpc)\b").unwrap();
+}
+
+impl Architecture {
+    /// Find the architecture token (eg. `Win64; x64`, `Linux armv7l`) and the bitness it implies.
+    ///
+    /// `WOW64` (a 32-bit browser on 64-bit Windows) is reported as 64-bit, since it's the
+    /// operating system that matters when picking an installer. Apple Silicon Macs still send
+    /// `Intel Mac OS X`, so only client hints can tell them apart.
+    pub fn parse(ua: &str) -> Option<(Architecture, u8)> {
+        use self::Architecture::*;
+
+        let captures = ARCHITECTURE_REGEX.captures(ua)?;
+        let token    = captures.at(1)?.to_lowercase();
+
+        let parsed = match token.as_str() {
+            "x86_64" | "amd64" | "x64" | "win64" | "wow64" | "intel mac os x" => (X64, 64),
+            "aarch64" | "arm64"                                               => (Arm64, 64),
+            "ppc64"                                                           => (PowerPC, 64),
+            "ppc" | "powerpc"                                                 => (PowerPC, 32),
+            token if token.starts_with("arm")                                 => (Arm, 32),
+            _                                                                 => (X86, 32),
+        };
+
+        Some(parsed)
+    }
+
+    /// Combine the `Sec-CH-UA-Arch` (`"x86"`, `"arm"`) and `Sec-CH-UA-Bitness` (`"64"`) client
+    /// hints. Either may be missing, and the quotes around the values are optional.
+    pub fn from_client_hints(arch: Option<&str>, bitness: Option<&str>) -> (Option<Architecture>, Option<u8>) {
+        use self::Architecture::*;
+
+        let unquote = |value: &str| value.trim().trim_matches('"').to_lowercase();
+        let bitness = bitness.and_then(|b| u8::from_str(&unquote(b)).ok()).filter(|&b| b == 32 || b == 64);
+
+        let architecture = arch.map(unquote).and_then(|arch| match (arch.as_str(), bitness) {
+            ("x86", Some(32)) => Some(X86),
+            ("x86", _)        => Some(X64),
+            ("arm", Some(32)) => Some(Arm),
+            ("arm", _)        => Some(Arm64),
+            _                 => None,
+        });
+
+        (architecture, bitness)
+    }
+}
+
+/// Since iPadOS 13, Safari on iPad requests desktop sites with the same user agent as Safari on
+/// a Mac (`Macintosh; Intel Mac OS X 10_15_7`), so the two can't be told apart from the user
+/// agent alone.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum IpadDesktopMode {
+    No,
+    /// Safari claiming macOS 10.15, the version iPadOS sends. Real Macs send it too.
+    Possibly,
+    /// Confirmed by a client signal such as `navigator.maxTouchPoints`
+    Yes,
+}
+
+impl IpadDesktopMode {
+    fn from_user_agent(ua: &str, name: &PlatformName, major: i16, minor: i16) -> IpadDesktopMode {
+        let safari = ua.contains("Version/") && ua.contains("Safari/") &&
+            !["Chrome/", "Firefox/", "Edg/", "OPR/"].iter().any(|token| ua.contains(token));
+
+        if *name == PlatformName::Mac && (major, minor) == (10, 15) && safari {
+            IpadDesktopMode::Possibly
+        } else {
+            IpadDesktopMode::No
+        }
+    }
 }
 
 #[derive(Debug, PartialEq)]
 pub struct Platform {
     pub name: PlatformName,
-    pub major_version: i8,
-    pub minor_version: i8,
+    pub major_version: i16,
+    pub minor_version: i16,
+    /// Only parsed for `PlatformName::Linux`
+    pub distribution: Option<Distribution>,
+    pub architecture: Option<Architecture>,
+    /// 32 or 64
+    pub bitness: Option<u8>,
+    /// The NT version and release, for `PlatformName::Windows`. `major_version` and
+    /// `minor_version` hold the marketing version (see `WindowsVersion::marketing_version`).
+    pub windows: Option<WindowsVersion>,
+    /// Whether this "Mac" could be an iPad requesting the desktop site
+    pub ipad_desktop_mode: IpadDesktopMode,
+    pub precision: Precision,
 }
 
 impl Platform {
     pub fn new(name: PlatformName,
-               major_version: i8,
-               minor_version: i8) -> Platform {
+               major_version: i16,
+               minor_version: i16) -> Platform {
         Platform {
             name: name,
             major_version: major_version,
             minor_version: minor_version,
+            distribution: None,
+            architecture: None,
+            bitness: None,
+            windows: None,
+            ipad_desktop_mode: IpadDesktopMode::No,
+            precision: Precision::Exact,
+        }
+    }
+
+    /// Marketing name, code name, API level and release date of the version, if it's in
+    /// `data/platform_versions.tsv`
+    pub fn version_name(&self) -> Option<&'static VersionName> {
+        if self.precision == Precision::Defaulted { return None }
+
+        version_names::lookup(&self.name, self.major_version, self.minor_version)
+    }
+
+    /// A human-readable name such as "Windows Vista", "OS X Mavericks", "Android KitKat (API 19)"
+    /// or "iOS 8". Versions that aren't in the table fall back to the platform and version.
+    /// Frozen versions only give a lower bound (`Mac OS X 10_15_7` is any macOS since
+    /// Catalina) and `Windows NT 10.0` may be Windows 11, so they're "macOS 10.15+" and "Windows
+    /// 10+" rather than a release's name.
+    pub fn display_name(&self) -> String {
+        if self.precision == Precision::Frozen {
+            return match self.minor_version {
+                0     => format!("{} {}+", self.name, self.major_version),
+                minor => format!("{} {}.{}+", self.name, self.major_version, minor),
+            }
+        }
+
+        if let Some(ref windows) = self.windows {
+            return windows.release.to_string()
+        }
+
+        if let Some(version_name) = self.version_name() {
+            return match version_name.api_level {
+                Some(level) => format!("{} (API {})", version_name.marketing_name, level),
+                None        => version_name.marketing_name.to_owned(),
+            }
+        }
+
+        match (self.precision, self.minor_version) {
+            (Precision::Defaulted, _) => self.name.to_string(),
+            (_, 0)                    => format!("{} {}", self.name, self.major_version),
+            (_, minor)                => format!("{} {}.{}", self.name, self.major_version, minor),
         }
     }
 
+    /// Settle whether a possible iPad in desktop mode really is one, using the page's
+    /// `navigator.maxTouchPoints`. Macs don't have touch screens and report 0; iPads report 5.
+    pub fn apply_max_touch_points(&mut self, max_touch_points: u32) {
+        if self.ipad_desktop_mode == IpadDesktopMode::No { return }
+
+        self.ipad_desktop_mode = if max_touch_points > 1 {
+            IpadDesktopMode::Yes
+        } else {
+            IpadDesktopMode::No
+        };
+    }
+
+    /// Apply the `Sec-CH-UA-Platform-Version` client hint. Currently this only matters for
+    /// Windows, where it's the only way to tell Windows 11 from Windows 10.
+    pub fn apply_platform_version_hint(&mut self, hint: &str) {
+        if let Some(ref mut windows) = self.windows {
+            windows.apply_platform_version_hint(hint);
+
+            let (major, minor) = windows.marketing_version();
+            self.major_version = major;
+            self.minor_version = minor;
+            self.precision     = Precision::Exact;
+        }
+    }
+
+    /// Override the architecture and bitness parsed from the user agent with the
+    /// `Sec-CH-UA-Arch` and `Sec-CH-UA-Bitness` client hints, where they were sent.
+    pub fn apply_client_hints(&mut self, arch: Option<&str>, bitness: Option<&str>) {
+        let (architecture, bitness) = Architecture::from_client_hints(arch, bitness);
+
+        if architecture.is_some() { self.architecture = architecture }
+        if bitness.is_some()      { self.bitness = bitness }
+    }
+
     pub fn parse(ua: &str) -> Option<Platform> {
         for &(ref match_pattern, ref name, major, minor) in MATCH_SEQUENCE.iter() {
             let mut major = major;
@@ -38,12 +366,11 @@ impl Platform {
             let matched = match match_pattern {
                 &MatchRegex(ref regex) => {
                     if let Some(captures) = regex.captures(ua) {
-                        // `len()` returns 3 when there are 2 capture groups
-                        // because it's including the full match as well
-                        // as a capture
-                        if captures.len() == 3 {
-                            major = i8::from_str(&captures[1]).unwrap();
-                            minor = i8::from_str(&captures[2]).unwrap();
+                        // Either version group can be optional (eg. `Tizen 3`), so only
+                        // override the defaults with what actually matched
+                        if let Some(version) = captures.at(1).and_then(|v| i16::from_str(v).ok()) {
+                            major = version;
+                            minor = captures.at(2).and_then(|v| i16::from_str(v).ok()).unwrap_or(0);
                         }
                         true
                     } else {
@@ -51,25 +378,81 @@ impl Platform {
                     }
                 },
                 &MatchString(ref string) => ua.contains(string),
+                &MatchFn(function) => {
+                    if let Some(versions) = function(ua) {
+                        major = versions.0;
+                        minor = versions.1;
+                        true
+                    } else {
+                        false
+                    }
+                },
             };
 
             if matched {
-                return Some(Platform::new(name.clone(), major, minor))
+                let mut platform = Platform::new(name.clone(), major, minor);
+                if *name == PlatformName::Linux {
+                    platform.distribution = Distribution::parse(ua);
+                }
+                if *name == PlatformName::Windows {
+                    platform.windows = WindowsVersion::parse(ua);
+                }
+                platform.ipad_desktop_mode = IpadDesktopMode::from_user_agent(ua, name, major, minor);
+                platform.precision = Platform::match_precision(&platform, ua);
+                if let Some((architecture, bitness)) = Architecture::parse(ua) {
+                    platform.architecture = Some(architecture);
+                    platform.bitness      = Some(bitness);
+                }
+                return Some(platform)
             }
         }
 
         None
     }
+
+    /// Browsers that reduce their user agent pin the platform to Windows 10 (Windows 11 still
+    /// sends `Windows NT 10.0`), macOS 10.15.7, ChromeOS 14541.0.0 and `Android 10; K`. Safari 26
+    /// pins iOS at 18.6.
+    fn match_precision(&self, ua: &str) -> Precision {
+        use self::PlatformName::*;
+
+        if self.major_version <= 0 && self.minor_version <= 0 {
+            return Precision::Defaulted
+        }
+
+        let frozen = match self.name {
+            Android  => ua.contains("Android 10; K)") || ua.contains("Android 10; K;"),
+            ChromeOS => self.major_version == 14541,
+            Mac      => ua.contains("Mac OS X 10_15_7") || FIREFOX_MAC_FROZEN_REGEX.is_match(ua),
+            Windows  => self.windows.as_ref().is_some_and(|w| w.release == WindowsRelease::Windows10),
+            IOS      => (self.major_version, self.minor_version) == (18, 6) && safari_major_version(ua) >= 26,
+            _ => false,
+        };
+
+        if frozen { Precision::Frozen } else { Precision::Exact }
+    }
+}
+
+/// The major version of Safari's `Version/` token, or 0 if there isn't one
+fn safari_major_version(ua: &str) -> i16 {
+    tokenize(ua).products()
+        .find(|product| product.name == "Version")
+        .and_then(|product| product.version)
+        .and_then(|version| i16::from_str(version.split('.').next()?).ok())
+        .unwrap_or(0)
 }
 
+#[allow(clippy::enum_variant_names)]
 enum MatchPattern {
     MatchRegex(Regex),
     MatchString(String),
+    /// For platforms whose version has to be derived rather than captured
+    MatchFn(fn(&str) -> Option<(i16, i16)>),
 }
 
 impl MatchPattern {
     fn with_regex(regex: &str) -> Result<MatchPattern, RegexError> {
-        Regex::new(regex).map(|regex| MatchPattern::MatchRegex(regex))
+        Regex::new(regex).map(MatchPattern::MatchRegex)
     }
 
     fn with_str(string: &str) -> MatchPattern {
@@ -77,38 +460,210 @@ impl MatchPattern {
     }
 }
 
-type MatchTuple = (MatchPattern, PlatformName, i8, i8);
+/// Firefox OS doesn't put its own version in the user agent, only the Gecko version it
+/// shipped with (`Mozilla/5.0 (Mobile; rv:26.0) Gecko/26.0 Firefox/26.0`).
+const FIREFOX_OS_VERSIONS: [((i16, i16), (i16, i16)); 9] = [
+    ((18, 0), (1, 0)),
+    ((18, 1), (1, 1)),
+    ((26, 0), (1, 2)),
+    ((28, 0), (1, 3)),
+    ((30, 0), (1, 4)),
+    ((32, 0), (2, 0)),
+    ((34, 0), (2, 1)),
+    ((37, 0), (2, 2)),
+    ((44, 0), (2, 5)),
+];
+
+fn match_windows(ua: &str) -> Option<(i16, i16)> {
+    WindowsVersion::parse(ua).map(|windows| windows.marketing_version())
+}
+
+fn match_firefox_os(ua: &str) -> Option<(i16, i16)> {
+    let captures = FIREFOX_OS_REGEX.captures(ua)?;
+    let gecko_version = (
+        captures.at(1).and_then(|v| i16::from_str(v).ok()).unwrap_or(0),
+        captures.at(2).and_then(|v| i16::from_str(v).ok()).unwrap_or(0),
+    );
+
+    let versions = FIREFOX_OS_VERSIONS.iter()
+        .rev()
+        .find(|&&(gecko, _)| gecko <= gecko_version)
+        .map_or((0, 0), |&(_, os)| os);
+
+    Some(versions)
+}
+
+/// The CFNetwork version each iOS release started at. Only used when there's no `Darwin/`
+/// token, since that maps to the iOS version directly.
+const CFNETWORK_IOS_VERSIONS: [(i16, i16); 14] = [
+    (548, 5),
+    (602, 6),
+    (672, 7),
+    (711, 8),
+    (758, 9),
+    (808, 10),
+    (887, 11),
+    (974, 12),
+    (1107, 13),
+    (1197, 14),
+    (1312, 15),
+    (1390, 16),
+    (1474, 17),
+    (1568, 18),
+];
+
+fn cfnetwork_ios_version(ua: &str) -> Option<i16> {
+    let cfnetwork = CFNETWORK_REGEX.captures(ua)?.at(1).and_then(|v| i16::from_str(v).ok())?;
+
+    CFNETWORK_IOS_VERSIONS.iter()
+        .rev()
+        .find(|&&(version, _)| version <= cfnetwork)
+        .map(|&(_, ios)| ios)
+}
+
+/// Native apps using Apple's networking stack send `MyApp/1.0 CFNetwork/1220.1 Darwin/20.3.0`.
+/// The Darwin version maps to an OS release; macOS also adds its architecture
+/// (`Darwin/20.3.0 (x86_64)`), which is how it's told apart from iOS. Darwin's minor version
+/// doesn't line up with iOS's, so only the iOS major version is reported.
+fn match_darwin_ios(ua: &str) -> Option<(i16, i16)> {
+    if !ua.contains("CFNetwork/") { return None }
+
+    let darwin = match DARWIN_REGEX.captures(ua) {
+        Some(ref captures) if captures.name("arch").is_some() => return None,
+        Some(captures) => captures.at(1).and_then(|v| i16::from_str(v).ok()),
+        None => None,
+    };
+
+    let ios = match darwin {
+        Some(darwin) if darwin >= 25 => darwin + 1, // iOS 26 followed iOS 18
+        Some(darwin) if darwin >= 15 => darwin - 6,
+        _ => cfnetwork_ios_version(ua)?, // Darwin 14 was both iOS 7 and 8
+    };
+
+    Some((ios, 0))
+}
+
+fn match_darwin_mac(ua: &str) -> Option<(i16, i16)> {
+    let captures = DARWIN_REGEX.captures(ua)?;
+    captures.name("arch")?;
+
+    let darwin = captures.at(1).and_then(|v| i16::from_str(v).ok())?;
+    let versions = match darwin {
+        25..=99 => (darwin + 1, 0), // macOS 26 followed macOS 15
+        20..=24 => (darwin - 9, 0),
+        5..=19  => (10, darwin - 4),
+        _ => return None,
+    };
+
+    Some(versions)
+}
+
+/// Native apps that report a hardware identifier (`MyApp/2.0 (iPhone12,1; iOS 14.2)`) rather
+/// than a browser-style `CPU iPhone OS 14_2 like Mac OS X`
+fn match_apple_identifier(ua: &str) -> Option<(i16, i16)> {
+    if !apple_devices::identifier(ua).is_some_and(apple_devices::is_ios_device) { return None }
+
+    let versions = IOS_VERSION_REGEX.captures(ua).map_or((0, 0), |captures| {
+        let major = captures.at(1).and_then(|v| i16::from_str(v).ok()).unwrap_or(0);
+        let minor = captures.at(2).and_then(|v| i16::from_str(v).ok()).unwrap_or(0);
+        (major, minor)
+    });
+
+    Some(versions)
+}
+
+type MatchTuple = (MatchPattern, PlatformName, i16, i16);
 
 lazy_static! {
+    // Firefox for Android starts its comment with `Android`, so it won't match
+    // Firefox freezes macOS at 10.15 without a patch version
+    static ref FIREFOX_MAC_FROZEN_REGEX: Regex = Regex::new(r"Mac OS X 10\.15[;)]").unwrap();
+
+    static ref CFNETWORK_REGEX: Regex = Regex::new(r"CFNetwork/(\d+)").unwrap();
+    static ref DARWIN_REGEX: Regex    = Regex::new(r"Darwin/(\d+)\.\d+(?:\.\d+)?(?P<arch> \((?:x86_64|arm64|i386)\))?").unwrap();
+
+    static ref IOS_VERSION_REGEX: Regex = Regex::new(r"\biOS (\d+)(?:[._](\d+))?").unwrap();
+
+    static ref FIREFOX_OS_REGEX: Regex = Regex::new(r"^Mozilla/5\.0 \((?:Mobile|Tablet|TV);(?: [^;)]+;)* rv:(\d+)(?:\.(\d+))?\) Gecko/").unwrap();
+
+    // NOTE: Order of tests is significant. Mobile platforms that also claim to be Android or
+    //       Linux (eg. `Windows Phone 8.1; Android 4.0`) have to be tested first.
     static ref MATCH_SEQUENCE: Vec<MatchTuple> = {
         use self::PlatformName::*;
 
-        let android_pattern = r"Android (\d+)\.(\d+)(?:\.\d+)?";
-        let ios_pattern     = r"CPU (?:iPhone )?OS (\d+)_(\d+)(?:_\d+)? like Mac OS X";
-        let mac_pattern     = r"Mac OS X (\d+)_(\d+)(?:_\d+)?";
+        let windows_phone_pattern = r"Windows Phone(?: OS)? (\d+)\.(\d+)";
+        let kaios_pattern         = r"KAIOS/(\d+)(?:\.(\d+))?";
+        let harmony_pattern       = r"(?:HarmonyOS|OpenHarmony)[ /](\d+)(?:\.(\d+))?";
+        let tizen_pattern         = r"Tizen (\d+)(?:\.(\d+))?";
+        let sailfish_pattern      = r"Sailfish (\d+)(?:\.(\d+))?";
+        let bb10_pattern          = r"BB10;.*Version/(\d+)\.(\d+)";
+        let playbook_pattern      = r"RIM Tablet OS (\d+)\.(\d+)";
+        let blackberry_pattern    = r"BlackBerry.*Version/(\d+)\.(\d+)";
+        let blackberry_pattern_2  = r"BlackBerry\w*/(\d+)\.(\d+)"; // `BlackBerry9700/5.0.0.862`
+        let symbian_pattern       = r"Symbian(?:OS)?/(\d+)(?:\.(\d+))?";
+        let webos_pattern         = r"(?:webOS|hpwOS)/(\d+)\.(\d+)";
+        let chromeos_pattern      = r"CrOS \w+ (\d+)\.(\d+)";
+        let solaris_pattern       = r"SunOS \w+ 5\.(\d+)"; // SunOS 5.11 is Solaris 11
+        let android_pattern       = r"Android (\d+)(?:\.(\d+))?(?:\.\d+)?"; // Reduced UAs send `Android 10; K`
+        let ios_pattern           = r"CPU (?:iPhone )?OS (\d+)_(\d+)(?:_\d+)? like Mac OS X";
+        let mac_pattern           = r"Mac OS X (\d+)[_.](\d+)(?:[_.]\d+)?"; // Firefox uses dots
 
         vec![
-            (MatchPattern::with_regex(android_pattern).unwrap(), Android, -1, -1),
-            (MatchPattern::with_regex(ios_pattern).unwrap(),     IOS,     -1, -1),
-            (MatchPattern::with_regex(mac_pattern).unwrap(),     Mac,     -1, -1),
-            (MatchPattern::with_str("Android"),                  Android, 0, 0),
-            (MatchPattern::with_str("Linux"),                    Linux,   0, 0),
-            (MatchPattern::with_str("Macintosh"),                Mac,     0, 0),
-            (MatchPattern::with_str("Windows XP"),               Windows, 5, 1),
-            (MatchPattern::with_str("Windows NT 5.1"),           Windows, 5, 1), // Also Windows XP
-            (MatchPattern::with_str("Windows NT 6.0"),           Windows, 6, 0), // Windows Vista
-            (MatchPattern::with_str("Windows NT 6.1"),           Windows, 7, 0),
-            (MatchPattern::with_str("Windows NT 6.2"),           Windows, 8, 0),
-            (MatchPattern::with_str("Windows NT 6.3"),           Windows, 8, 1),
-            (MatchPattern::with_str("Windows NT 10.0"),          Windows, 10, 0),
-            (MatchPattern::with_str("Windows"),                  Windows, 0, 0), // Match any other Windows
+            (MatchPattern::with_regex(windows_phone_pattern).unwrap(), WindowsPhone, -1, -1),
+            (MatchPattern::with_str("Windows Phone"),                  WindowsPhone, 0, 0),
+            (MatchPattern::with_regex(kaios_pattern).unwrap(),         KaiOS,        -1, -1),
+            (MatchPattern::with_str("KAIOS"),                          KaiOS,        0, 0),
+            (MatchPattern::with_regex(harmony_pattern).unwrap(),       HarmonyOS,    -1, -1),
+            (MatchPattern::with_str("HarmonyOS"),                      HarmonyOS,    0, 0),
+            (MatchPattern::with_str("OpenHarmony"),                    HarmonyOS,    0, 0),
+            (MatchPattern::with_regex(tizen_pattern).unwrap(),         Tizen,        -1, -1),
+            (MatchPattern::with_str("Tizen"),                          Tizen,        0, 0),
+            (MatchPattern::with_regex(sailfish_pattern).unwrap(),      Sailfish,     -1, -1),
+            (MatchPattern::with_str("Sailfish"),                       Sailfish,     0, 0),
+            (MatchPattern::with_regex(bb10_pattern).unwrap(),          BlackBerry,   -1, -1),
+            (MatchPattern::with_regex(playbook_pattern).unwrap(),      BlackBerry,   -1, -1),
+            (MatchPattern::with_regex(blackberry_pattern).unwrap(),    BlackBerry,   -1, -1),
+            (MatchPattern::with_regex(blackberry_pattern_2).unwrap(),  BlackBerry,   -1, -1),
+            (MatchPattern::with_str("BB10"),                           BlackBerry,   10, 0),
+            (MatchPattern::with_str("BlackBerry"),                     BlackBerry,   0, 0),
+            (MatchPattern::with_regex(symbian_pattern).unwrap(),       Symbian,      -1, -1),
+            (MatchPattern::with_str("SymbOS"),                         Symbian,      0, 0),
+            (MatchPattern::with_str("Symbian"),                        Symbian,      0, 0),
+            (MatchPattern::with_str("Series60"),                       Symbian,      0, 0),
+            (MatchPattern::with_regex(webos_pattern).unwrap(),         WebOS,        -1, -1),
+            (MatchPattern::with_str("webOS"),                          WebOS,        0, 0),
+            (MatchPattern::with_str("Web0S"),                          WebOS,        0, 0), // LG smart TVs
+            (MatchFn(match_firefox_os),                                FirefoxOS,    0, 0),
+            (MatchFn(match_darwin_mac),                                Mac,          0, 0),
+            (MatchFn(match_darwin_ios),                                IOS,          0, 0),
+            (MatchPattern::with_regex(chromeos_pattern).unwrap(),      ChromeOS,     -1, -1),
+            (MatchPattern::with_str("CrOS"),                           ChromeOS,     0, 0),
+            (MatchPattern::with_regex(android_pattern).unwrap(),       Android,      -1, -1),
+            (MatchPattern::with_regex(ios_pattern).unwrap(),           IOS,          -1, -1),
+            (MatchFn(match_apple_identifier),                          IOS,          0, 0),
+            (MatchPattern::with_regex(mac_pattern).unwrap(),           Mac,          -1, -1),
+            (MatchPattern::with_str("Android"),                        Android,      0, 0),
+            (MatchPattern::with_str("FreeBSD"),                        FreeBSD,      0, 0),
+            (MatchPattern::with_str("OpenBSD"),                        OpenBSD,      0, 0),
+            (MatchPattern::with_str("NetBSD"),                         NetBSD,       0, 0),
+            (MatchPattern::with_regex(solaris_pattern).unwrap(),       Solaris,      -1, -1),
+            (MatchPattern::with_str("SunOS"),                          Solaris,      0, 0),
+            (MatchPattern::with_str("Solaris"),                        Solaris,      0, 0),
+            (MatchPattern::with_str("Linux"),                          Linux,        0, 0),
+            (MatchPattern::with_str("Macintosh"),                      Mac,          0, 0),
+            (MatchPattern::with_str("Windows XP"),                     Windows,      5, 1),
+            (MatchFn(match_windows),                                   Windows,      0, 0),
+            (MatchPattern::with_str("Windows"),                        Windows,      0, 0), // Match any other Windows
         ]
     };
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Platform;
+    use super::{Architecture, Distribution, DistributionName, IpadDesktopMode, Platform, PlatformName};
+    use precision::Precision;
+    use windows::WindowsRelease;
+    use super::Architecture::*;
     use super::PlatformName::*;
 
     const ANDROID_444: &'static str = "Mozilla/5.0 (Linux; Android 4.4.4; One Build/KTU84L.H4) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/36.0.1985.135 Mobile Safari/537.36";
@@ -116,6 +671,32 @@ mod tests {
     const IOS_802: &'static str     = "Mozilla/5.0 (iPhone; CPU iPhone OS 8_0_2 like Mac OS X) AppleWebKit/600.1.4 (KHTML, like Gecko) Version/8.0 Mobile/12A366 Safari/600.1.4";
     const MAC_1093: &'static str    = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_9_3) AppleWebKit/537.75.14 (KHTML, like Gecko) Version/7.0.3 Safari/7046A194A";
     const WINDOWS_81: &'static str  = "Mozilla/5.0 (Windows NT 6.3; Trident/7.0; rv:11.0) like Gecko";
+    const SAFARI_MAC: &'static str  = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.0 Safari/605.1.15";
+    const REDUCED: &'static str     = "Mozilla/5.0 (Linux; Android 10; K) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Mobile Safari/537.36";
+    const SAFARI_26: &'static str   = "Mozilla/5.0 (iPhone; CPU iPhone OS 18_6 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/26.0 Mobile/15E148 Safari/604.1";
+    const SAFARI_18_6: &'static str = "Mozilla/5.0 (iPhone; CPU iPhone OS 18_6 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/18.6 Mobile/15E148 Safari/604.1";
+    const FIREFOX_MAC: &'static str = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10.15; rv:120.0) Gecko/20100101 Firefox/120.0";
+    const SURFACE_RT: &'static str  = "Mozilla/5.0 (compatible; MSIE 10.0; Windows NT 6.2; ARM; Trident/6.0; Touch)";
+    const WINDOWS_10: &'static str  = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+    const WP_81: &'static str       = "Mozilla/5.0 (Mobile; Windows Phone 8.1; Android 4.0; ARM; Trident/7.0; Touch; rv:11.0; IEMobile/11.0; NOKIA; Lumia 635) like iPhone OS 7_0_3 Mac OS X AppleWebKit/537 (KHTML, like Gecko) Mobile Safari/537";
+    const BB10: &'static str        = "Mozilla/5.0 (BB10; Touch) AppleWebKit/537.10+ (KHTML, like Gecko) Version/10.0.9.2372 Mobile Safari/537.10+";
+    const BLACKBERRY: &'static str  = "BlackBerry9700/5.0.0.862 Profile/MIDP-2.1 Configuration/CLDC-1.1 VendorID/331";
+    const OPERA_MINI: &'static str  = "Opera/9.80 (J2ME/MIDP; Opera Mini/9.80 (S60; SymbOS; Opera Mobi/23.348; U; en) Presto/2.5.25 Version/10.54";
+    const SYMBIAN_94: &'static str  = "Mozilla/5.0 (SymbianOS/9.4; Series60/5.0 NokiaN97-1/12.0.024; Profile/MIDP-2.1 Configuration/CLDC-1.1; en-us) AppleWebKit/525 (KHTML, like Gecko) BrowserNG/7.1.18124";
+    const KAIOS_25: &'static str    = "Mozilla/5.0 (Mobile; LYF/F300B/LYF-F300B-001-01-15-130718-i; Android; rv:48.0) Gecko/48.0 Firefox/48.0 KAIOS/2.5";
+    const TIZEN_3: &'static str     = "Mozilla/5.0 (Linux; Tizen 3.0; SAMSUNG SM-Z400Y) AppleWebKit/537.36 (KHTML, like Gecko) SamsungBrowser/1.0 Chrome/47.0.2526.69 Mobile safari/537.36";
+    const HARMONY: &'static str     = "Mozilla/5.0 (Phone; OpenHarmony 4.0) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/114.0.0.0 Safari/537.36 ArkWeb/4.1.6.1 Mobile";
+    const WEBOS: &'static str       = "Mozilla/5.0 (webOS/1.4.0; U; en-US) AppleWebKit/532.2 (KHTML, like Gecko) Version/1.0 Safari/532.2 Pre/1.0";
+    const FIREFOX_OS: &'static str  = "Mozilla/5.0 (Mobile; ZTEOPEN; rv:18.1) Gecko/18.0 Firefox/18.1";
+    const FIREFOX_AND: &'static str = "Mozilla/5.0 (Android 4.4; Mobile; rv:41.0) Gecko/41.0 Firefox/41.0";
+    const CHROMEOS: &'static str    = "Mozilla/5.0 (X11; CrOS x86_64 14541.0.0) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+    const FREEBSD: &'static str     = "Mozilla/5.0 (X11; FreeBSD amd64; rv:109.0) Gecko/20100101 Firefox/115.0";
+    const SOLARIS: &'static str     = "Mozilla/5.0 (X11; SunOS i86pc 5.11; rv:52.0) Gecko/20100101 Firefox/52.0";
+    const UBUNTU: &'static str      = "Opera/9.80 (X11; Linux i686; Ubuntu/14.10) Presto/2.12.388 Version/12.16";
+    const FEDORA: &'static str      = "Mozilla/5.0 (X11; U; Linux x86_64; en-US; rv:1.9.2.3) Gecko/20100403 Fedora/3.6.3-4.fc13 Firefox/3.6.3";
+    const SAILFISH: &'static str    = "Mozilla/5.0 (Linux; U; Sailfish 3.0; Mobile; rv:45.0) Gecko/45.0 Firefox/45.0 SailfishBrowser/1.0";
+    const IOS_APP: &'static str     = "MyApp/3.2.1 CFNetwork/1220.1 Darwin/20.3.0";
+    const MAC_APP: &'static str     = "MyApp/3.2.1 CFNetwork/1220.1 Darwin/20.3.0 (x86_64)";
 
     #[test]
     fn matches_android_444() {
@@ -145,15 +726,193 @@ mod tests {
     fn matches_mac1093() {
         assert_eq!(
             Platform::parse(MAC_1093),
-            Some(Platform::new(Mac, 10, 9))
+            Some(Platform { architecture: Some(X64), bitness: Some(64), ..Platform::new(Mac, 10, 9) })
         )
     }
 
     #[test]
     fn matches_windows_81() {
         assert_eq!(
-            Platform::parse(WINDOWS_81),
-            Some(Platform::new(Windows, 8, 1))
+            Platform::parse(WINDOWS_81).map(|p| (p.name, p.major_version, p.minor_version)),
+            Some((Windows, 8, 1))
         )
     }
+
+    #[test]
+    fn matches_windows_phone_before_android() {
+        assert_eq!(
+            Platform::parse(WP_81),
+            Some(Platform { architecture: Some(Arm), bitness: Some(32), ..Platform::new(WindowsPhone, 8, 1) })
+        )
+    }
+
+    #[test]
+    fn matches_blackberry() {
+        assert_eq!(Platform::parse(BB10), Some(Platform::new(BlackBerry, 10, 0)));
+        assert_eq!(Platform::parse(BLACKBERRY), Some(Platform::new(BlackBerry, 5, 0)));
+    }
+
+    #[test]
+    fn matches_symbian() {
+        assert_eq!(Platform::parse(OPERA_MINI).map(|p| (p.name, p.precision)), Some((Symbian, Precision::Defaulted)));
+        assert_eq!(Platform::parse(SYMBIAN_94), Some(Platform::new(Symbian, 9, 4)));
+    }
+
+    #[test]
+    fn matches_mobile_linux_derivatives() {
+        assert_eq!(Platform::parse(KAIOS_25), Some(Platform::new(KaiOS, 2, 5)));
+        assert_eq!(Platform::parse(TIZEN_3), Some(Platform::new(Tizen, 3, 0)));
+        assert_eq!(Platform::parse(HARMONY), Some(Platform::new(HarmonyOS, 4, 0)));
+        assert_eq!(Platform::parse(WEBOS), Some(Platform::new(WebOS, 1, 4)));
+        assert_eq!(Platform::parse(SAILFISH), Some(Platform::new(Sailfish, 3, 0)));
+    }
+
+    #[test]
+    fn matches_firefox_os_from_gecko_version() {
+        assert_eq!(Platform::parse(FIREFOX_OS), Some(Platform::new(FirefoxOS, 1, 1)));
+        assert_eq!(Platform::parse(FIREFOX_AND), Some(Platform::new(Android, 4, 4)));
+    }
+
+    #[test]
+    fn matches_chromeos_with_build_version() {
+        assert_eq!(Platform::parse(CHROMEOS), Some(Platform {
+                architecture: Some(X64),
+                bitness:      Some(64),
+                precision:    Precision::Frozen,
+                ..Platform::new(ChromeOS, 14541, 0)
+            }))
+    }
+
+    #[test]
+    fn matches_bsd_and_solaris() {
+        let freebsd = Platform::parse(FREEBSD).unwrap();
+
+        assert_eq!((freebsd.major_version, freebsd.architecture), (0, Some(X64)));
+        assert!(freebsd.name.is_bsd());
+        assert_eq!(Platform::parse(SOLARIS), Some(Platform::new(Solaris, 11, 0)));
+    }
+
+    #[test]
+    fn matches_linux_distribution() {
+        let ubuntu = Platform::parse(UBUNTU).unwrap();
+        let fedora = Platform::parse(FEDORA).unwrap();
+
+        assert_eq!(ubuntu.name, Linux);
+        assert_eq!(ubuntu.distribution, Some(Distribution::new(DistributionName::Ubuntu, 14, 10)));
+        assert_eq!(fedora.distribution, Some(Distribution::new(DistributionName::Fedora, 13, 0)));
+        assert_eq!(Platform::parse(ANDROID_444).unwrap().distribution, None);
+    }
+
+    #[test]
+    fn matches_architecture() {
+        let arch = |ua| Architecture::parse(ua);
+
+        assert_eq!(arch("Mozilla/5.0 (Windows NT 10.0; Win64; x64)"), Some((X64, 64)));
+        assert_eq!(arch("Mozilla/5.0 (Windows NT 6.1; WOW64; rv:40.0)"), Some((X64, 64)));
+        assert_eq!(arch("Mozilla/5.0 (X11; Linux i686; rv:109.0)"), Some((X86, 32)));
+        assert_eq!(arch("Mozilla/5.0 (X11; Linux aarch64)"), Some((Arm64, 64)));
+        assert_eq!(arch("Mozilla/5.0 (X11; Linux armv7l)"), Some((Arm, 32)));
+        assert_eq!(arch("Mozilla/5.0 (Macintosh; U; PPC Mac OS X 10_5_8)"), Some((PowerPC, 32)));
+        assert_eq!(arch(ANDROID_444), None);
+    }
+
+    #[test]
+    fn client_hints_override_user_agent() {
+        let mut mac = Platform::parse(MAC_1093).unwrap();
+        mac.apply_client_hints(Some("\"arm\""), Some("\"64\""));
+
+        assert_eq!((mac.architecture, mac.bitness), (Some(Arm64), Some(64)));
+        assert_eq!(Architecture::from_client_hints(Some("x86"), Some("32")), (Some(X86), Some(32)));
+        assert_eq!(Architecture::from_client_hints(None, Some("64")), (None, Some(64)));
+    }
+
+    #[test]
+    fn windows_reports_nt_and_marketing_versions() {
+        let mut windows = Platform::parse(WINDOWS_10).unwrap();
+        assert_eq!((windows.major_version, windows.minor_version), (10, 0));
+
+        windows.apply_platform_version_hint("\"15.0.0\"");
+        let nt = windows.windows.clone().unwrap();
+
+        assert_eq!((windows.major_version, windows.minor_version), (11, 0));
+        assert_eq!((nt.nt_major_version, nt.nt_minor_version, nt.release), (10, 0, WindowsRelease::Windows11));
+    }
+
+    #[test]
+    fn possible_ipad_in_desktop_mode() {
+        let mut safari = Platform::parse(SAFARI_MAC).unwrap();
+        assert_eq!(safari.ipad_desktop_mode, IpadDesktopMode::Possibly);
+        assert_eq!(Platform::parse(MAC_1093).unwrap().ipad_desktop_mode, IpadDesktopMode::No);
+
+        safari.apply_max_touch_points(5);
+        assert_eq!(safari.ipad_desktop_mode, IpadDesktopMode::Yes);
+
+        let mut mac = Platform::parse(SAFARI_MAC).unwrap();
+        mac.apply_max_touch_points(0);
+        assert_eq!(mac.ipad_desktop_mode, IpadDesktopMode::No);
+    }
+
+    #[test]
+    fn marks_frozen_and_defaulted_versions() {
+        let reduced = Platform::parse(REDUCED).unwrap();
+        assert_eq!((reduced.name, reduced.major_version, reduced.precision), (Android, 10, Precision::Frozen));
+
+        let firefox = Platform::parse(FIREFOX_MAC).unwrap();
+        assert_eq!((firefox.major_version, firefox.minor_version, firefox.precision), (10, 15, Precision::Frozen));
+
+        let webview = Platform::parse("Mozilla/5.0 (Linux; Android 10; K; wv) AppleWebKit/537.36 (KHTML, like Gecko) Version/4.0 Chrome/120.0.0.0 Mobile Safari/537.36").unwrap();
+        assert_eq!(webview.precision, Precision::Frozen);
+
+        let safari_26 = Platform::parse(SAFARI_26).unwrap();
+        assert_eq!((safari_26.major_version, safari_26.minor_version, safari_26.precision), (18, 6, Precision::Frozen));
+        assert_eq!(Platform::parse(SAFARI_18_6).unwrap().precision, Precision::Exact);
+
+        assert_eq!(Platform::parse(ANDROID_444).unwrap().precision, Precision::Exact);
+        assert_eq!(Platform::parse(WINDOWS_10).unwrap().precision, Precision::Frozen);
+        assert_eq!(Platform::parse("Mozilla/5.0 (Linux)").unwrap().precision, Precision::Defaulted);
+    }
+
+    #[test]
+    fn display_names() {
+        let name = |ua| Platform::parse(ua).unwrap().display_name();
+
+        assert_eq!(name(MAC_1093), "OS X Mavericks");
+        assert_eq!(name(ANDROID_444), "Android KitKat (API 19)");
+        assert_eq!(name(IOS_802), "iOS 8");
+        assert_eq!(name(WINDOWS_81), "Windows 8.1");
+        // Windows 11 still sends `Windows NT 10.0`
+        assert_eq!(name(WINDOWS_10), "Windows 10+");
+        assert_eq!(name(SURFACE_RT), "Windows RT");
+        assert_eq!(name(OPERA_MINI), "Symbian");
+        assert_eq!(name(KAIOS_25), "KaiOS 2.5");
+        assert_eq!(name(FIREFOX_MAC), "macOS 10.15+");
+        assert_eq!(name(REDUCED), "Android 10+");
+        assert_eq!(name(SAFARI_26), "iOS 18.6+");
+        assert_eq!("WindowsPhone".parse(), Ok(PlatformName::WindowsPhone));
+    }
+
+    #[test]
+    fn parses_cfnetwork_darwin_versions() {
+        let ios = Platform::parse(IOS_APP).unwrap();
+        assert_eq!((ios.name, ios.major_version, ios.minor_version), (IOS, 14, 0));
+
+        let mac = Platform::parse(MAC_APP).unwrap();
+        assert_eq!((mac.name, mac.major_version, mac.architecture), (Mac, 11, Some(Architecture::X64)));
+
+        let version = |ua| Platform::parse(ua).map(|p| (p.name, p.major_version, p.minor_version));
+        assert_eq!(version("Foo/1 CFNetwork/1568.100.1 Darwin/25.0.0"), Some((IOS, 26, 0)));
+        assert_eq!(version("Foo/1 CFNetwork/711.1.16 Darwin/14.0.0"), Some((IOS, 8, 0)));
+        assert_eq!(version("Foo/1 CFNetwork/1107.1"), Some((IOS, 13, 0)));
+        assert_eq!(version("Foo/1 CFNetwork/978.0.7 Darwin/18.7.0 (x86_64)"), Some((Mac, 10, 14)));
+        assert_eq!(version("Foo/1.0 Darwin/20.3.0"), None);
+    }
+
+    #[test]
+    fn parses_apple_hardware_identifiers() {
+        let version = |ua| Platform::parse(ua).map(|p| (p.name, p.major_version, p.minor_version));
+
+        assert_eq!(version("MyApp/2.0 (iPhone12,1; iOS 14.2; Scale/2.00)"), Some((IOS, 14, 2)));
+        assert_eq!(version("MyApp/2.0 (iPad8,6)"), Some((IOS, 0, 0)));
+        assert_eq!(version("MyApp/2.0 (Watch6,2; watchOS 7.0)"), None);
+    }
 }
@@ -0,0 +1,11 @@
+/// How far a parsed version can be trusted.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Precision {
+    /// Sent as-is by the client
+    Exact,
+    /// Frozen by user agent reduction (eg. Chrome's `120.0.0.0` or `Android 10; K`), so the real
+    /// version may differ. For platforms the real version is usually newer.
+    Frozen,
+    /// Nothing was sent; the version is a `0.0` or `-1.-1` placeholder, or the value is a default
+    Defaulted,
+}
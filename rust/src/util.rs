@@ -2,9 +2,8 @@ use regex::{Captures};
 use std::str::FromStr;
 
 /// Takes the first two capture groups from a regex result and turns them into a version
-/// integer 2-tuple
-pub fn map_first_captures(captures: Captures) -> (i8, i8) {
-    let major_version = i8::from_str(&captures[1]).unwrap();
-    let minor_version = i8::from_str(&captures[2]).unwrap();
-    (major_version, minor_version)
+/// integer 2-tuple. Versions too big for an `i16` (`Chrome/99999.0`) become zero.
+pub fn map_first_captures(captures: Captures) -> (i16, i16) {
+    let version = |index| captures.at(index).and_then(|v| i16::from_str(v).ok()).unwrap_or(0);
+    (version(1), version(2))
 }
@@ -0,0 +1,524 @@
+use libc;
+use regex::{Regex};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::ffi::CStr;
+use std::fs::File;
+use std::io::{self, Read};
+use std::mem;
+use std::net::{IpAddr, ToSocketAddrs};
+use std::path::Path;
+use std::ptr;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use bot::{Bot, BotName};
+
+/// DNS lookups needed to verify a crawler. Implement this to plug in a caching or async
+/// resolver; `SystemResolver` uses the operating system's resolver.
+pub trait Resolver {
+    /// Look up the hostnames (PTR records) for an address
+    fn reverse(&self, ip: IpAddr) -> io::Result<Vec<String>>;
+
+    /// Look up the addresses (A/AAAA records) for a hostname
+    fn forward(&self, hostname: &str) -> io::Result<Vec<IpAddr>>;
+}
+
+/// BSD-derived systems expect the length of the `sockaddr` to be set in the struct itself.
+macro_rules! set_sockaddr_len {
+    ($addr:ident, $field:ident, $kind:ty) => {
+        #[cfg(any(target_os = "macos", target_os = "ios", target_os = "freebsd",
+                  target_os = "openbsd", target_os = "netbsd", target_os = "dragonfly"))]
+        {
+            $addr.$field = mem::size_of::<$kind>() as u8;
+        }
+    };
+}
+
+/// Resolves through `getnameinfo(3)` and `getaddrinfo(3)`. These block the calling thread.
+pub struct SystemResolver;
+
+impl Resolver for SystemResolver {
+    fn reverse(&self, ip: IpAddr) -> io::Result<Vec<String>> {
+        // NI_MAXHOST
+        let mut host = [0 as libc::c_char; 1025];
+
+        let result = unsafe {
+            match ip {
+                IpAddr::V4(v4) => {
+                    let mut addr: libc::sockaddr_in = mem::zeroed();
+                    addr.sin_family = libc::AF_INET as libc::sa_family_t;
+                    addr.sin_addr   = libc::in_addr { s_addr: u32::from(v4).to_be() };
+                    set_sockaddr_len!(addr, sin_len, libc::sockaddr_in);
+
+                    libc::getnameinfo(
+                        &addr as *const _ as *const libc::sockaddr,
+                        mem::size_of::<libc::sockaddr_in>() as libc::socklen_t,
+                        host.as_mut_ptr(), host.len() as libc::socklen_t,
+                        ptr::null_mut(), 0,
+                        libc::NI_NAMEREQD,
+                    )
+                },
+                IpAddr::V6(v6) => {
+                    let mut addr: libc::sockaddr_in6 = mem::zeroed();
+                    addr.sin6_family = libc::AF_INET6 as libc::sa_family_t;
+                    addr.sin6_addr.s6_addr = v6.octets();
+                    set_sockaddr_len!(addr, sin6_len, libc::sockaddr_in6);
+
+                    libc::getnameinfo(
+                        &addr as *const _ as *const libc::sockaddr,
+                        mem::size_of::<libc::sockaddr_in6>() as libc::socklen_t,
+                        host.as_mut_ptr(), host.len() as libc::socklen_t,
+                        ptr::null_mut(), 0,
+                        libc::NI_NAMEREQD,
+                    )
+                },
+            }
+        };
+
+        match result {
+            0 => {
+                let hostname = unsafe { CStr::from_ptr(host.as_ptr()) };
+                Ok(vec![hostname.to_string_lossy().into_owned()])
+            },
+            libc::EAI_NONAME => Err(io::Error::new(io::ErrorKind::NotFound, "no PTR record")),
+            _ => Err(io::Error::other(format!("getnameinfo failed ({})", result))),
+        }
+    }
+
+    fn forward(&self, hostname: &str) -> io::Result<Vec<IpAddr>> {
+        (hostname, 0).to_socket_addrs()
+            .map(|addrs| addrs.map(|addr| addr.ip()).collect())
+    }
+}
+
+/// The domains that a bot's reverse DNS must fall under, as documented by each operator.
+/// Bots that aren't listed here can only be verified with `IpRanges`.
+pub fn hostname_suffixes(name: &BotName) -> &'static [&'static str] {
+    use bot::BotName::*;
+
+    match *name {
+        Amazonbot                   => &["crawl.amazonbot.amazon"],
+        ApplebotExtended            => &["applebot.apple.com"],
+        Baidu                       => &["crawl.baidu.com", "crawl.baidu.jp"],
+        Bingbot                     => &["search.msn.com"],
+        Googlebot | GoogleExtended  => &["googlebot.com", "google.com", "googleusercontent.com"],
+        Pinterestbot                => &["pinterest.com"],
+        _ => &[],
+    }
+}
+
+fn has_suffix(hostname: &str, suffix: &str) -> bool {
+    let hostname = hostname.trim_end_matches('.').to_lowercase();
+
+    hostname == suffix || hostname.ends_with(&format!(".{}", suffix))
+}
+
+/// A CIDR block such as `66.249.64.0/27` or `2001:4860:4801:10::/64`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IpRange {
+    pub network: IpAddr,
+    pub prefix: u8,
+}
+
+impl IpRange {
+    pub fn contains(&self, ip: &IpAddr) -> bool {
+        match (self.network, *ip) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => {
+                let mask = prefix_mask(self.prefix, 32) as u32;
+                u32::from(network) & mask == u32::from(ip) & mask
+            },
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                let mask = prefix_mask(self.prefix, 128);
+                u128::from(network) & mask == u128::from(ip) & mask
+            },
+            _ => false,
+        }
+    }
+}
+
+/// Netmask for the first `prefix` bits of a `bits`-wide address
+fn prefix_mask(prefix: u8, bits: u32) -> u128 {
+    let all_bits  = !0u128 >> (128 - bits);
+    let host_bits = bits - u32::from(prefix).min(bits);
+
+    all_bits & all_bits.checked_shl(host_bits).unwrap_or(0)
+}
+
+impl FromStr for IpRange {
+    type Err = io::Error;
+
+    /// Parses `address/prefix`. A bare address is treated as a single-host range.
+    fn from_str(string: &str) -> io::Result<IpRange> {
+        let invalid = || io::Error::new(io::ErrorKind::InvalidData, format!("invalid IP range: {}", string));
+        let mut parts = string.trim().splitn(2, '/');
+
+        let network = parts.next().and_then(|p| IpAddr::from_str(p).ok()).ok_or_else(&invalid)?;
+        let max_prefix = if network.is_ipv4() { 32 } else { 128 };
+        let prefix = match parts.next() {
+            Some(p) => u8::from_str(p).ok().filter(|p| *p <= max_prefix).ok_or_else(&invalid)?,
+            None => max_prefix,
+        };
+
+        Ok(IpRange {
+            network: network,
+            prefix:  prefix,
+        })
+    }
+}
+
+lazy_static! {
+    static ref JSON_PREFIX_REGEX: Regex = Regex::new(r#""ipv[46]Prefix"\s*:\s*"([^"]+)""#).unwrap();
+}
+
+/// A published list of IP ranges for a crawler.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IpRanges(pub Vec<IpRange>);
+
+impl IpRanges {
+    /// Parses either the JSON format published by Google, Bing, OpenAI et al.
+    /// (`{"prefixes": [{"ipv4Prefix": "..."}]}`) or plain text with one range per line. Blank
+    /// lines and lines starting with `#` are ignored in plain text.
+    pub fn parse(string: &str) -> io::Result<IpRanges> {
+        let mut ranges = vec![];
+
+        if string.trim_start().starts_with('{') {
+            for captures in JSON_PREFIX_REGEX.captures_iter(string) {
+                ranges.push(IpRange::from_str(&captures[1])?);
+            }
+        } else {
+            for line in string.lines().map(str::trim) {
+                if line.is_empty() || line.starts_with('#') { continue }
+
+                ranges.push(IpRange::from_str(line)?);
+            }
+        }
+
+        Ok(IpRanges(ranges))
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<IpRanges> {
+        let mut string = String::new();
+        File::open(path)?.read_to_string(&mut string)?;
+
+        IpRanges::parse(&string)
+    }
+
+    pub fn contains(&self, ip: &IpAddr) -> bool {
+        self.0.iter().any(|range| range.contains(ip))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Verification {
+    /// The reverse lookup pointed at one of the bot's domains and the forward lookup of that
+    /// hostname confirmed the address
+    VerifiedHostname(String),
+    /// The address is in one of the bot's published IP ranges
+    VerifiedIpRange,
+    /// The client is claiming to be a bot that it isn't
+    Failed,
+    /// A DNS lookup failed for a reason other than the record not existing; not cached
+    LookupFailed,
+    /// There's no way to verify this bot: it has no known hostnames and no IP ranges were added
+    Unverifiable,
+}
+
+impl Verification {
+    pub fn is_verified(&self) -> bool {
+        matches!(*self, Verification::VerifiedHostname(_) | Verification::VerifiedIpRange)
+    }
+}
+
+type CacheKey = (BotName, IpAddr);
+
+/// Verification results and when they expire
+#[derive(Default)]
+struct ResultCache {
+    results: HashMap<CacheKey, (Verification, Instant)>,
+    /// Every result's expiry, soonest first. Entries for results that have since been replaced or
+    /// evicted are skipped when they come up.
+    expiries: BinaryHeap<Reverse<(Instant, CacheKey)>>,
+}
+
+impl ResultCache {
+    fn get(&self, key: &CacheKey, now: Instant) -> Option<&Verification> {
+        match self.results.get(key) {
+            Some(&(ref verification, expires_at)) if now < expires_at => Some(verification),
+            _ => None,
+        }
+    }
+
+    /// Drop the result that expires soonest. False if there's nothing left to drop.
+    fn pop_soonest(&mut self) -> bool {
+        let Reverse((expires_at, key)) = match self.expiries.pop() {
+            Some(soonest) => soonest,
+            None          => return false,
+        };
+
+        if self.results.get(&key).is_some_and(|&(_, current)| current == expires_at) {
+            self.results.remove(&key);
+        }
+        true
+    }
+
+    /// Drop expired results, then the ones closest to expiring while the cache is still full.
+    /// Failures have the shorter TTL, so they go before verified crawlers do.
+    fn insert(&mut self, key: CacheKey, verification: Verification, expires_at: Instant, now: Instant, capacity: usize) {
+        while self.expiries.peek().is_some_and(|&Reverse((soonest, _))| soonest <= now) {
+            self.pop_soonest();
+        }
+        while self.results.len() >= capacity && !self.results.contains_key(&key) {
+            if !self.pop_soonest() { break }
+        }
+
+        self.results.insert(key.clone(), (verification, expires_at));
+        self.expiries.push(Reverse((expires_at, key)));
+    }
+
+    fn clear(&mut self) {
+        self.results.clear();
+        self.expiries.clear();
+    }
+}
+
+/// Verifies that clients claiming to be a crawler really are that crawler, following each
+/// operator's documented reverse-then-forward DNS check and/or published IP ranges.
+pub struct Verifier<R: Resolver> {
+    resolver: R,
+    ranges: HashMap<BotName, IpRanges>,
+    cache: Mutex<ResultCache>,
+    /// How long verified results are cached for
+    positive_ttl: Duration,
+    /// How long failed results are cached for
+    negative_ttl: Duration,
+    /// Most results kept at once. Anyone can claim to be a crawler from a new address, so the
+    /// cache has to be bounded.
+    cache_capacity: usize,
+}
+
+impl<R: Resolver> Verifier<R> {
+    pub fn new(resolver: R) -> Verifier<R> {
+        Verifier {
+            resolver:       resolver,
+            ranges:         HashMap::new(),
+            cache:          Mutex::new(ResultCache::default()),
+            positive_ttl:   Duration::from_secs(24 * 60 * 60),
+            negative_ttl:   Duration::from_secs(60 * 60),
+            cache_capacity: 10_000,
+        }
+    }
+
+    pub fn with_ttls(mut self, positive_ttl: Duration, negative_ttl: Duration) -> Verifier<R> {
+        self.positive_ttl = positive_ttl;
+        self.negative_ttl = negative_ttl;
+        self
+    }
+
+    pub fn with_cache_capacity(mut self, cache_capacity: usize) -> Verifier<R> {
+        self.cache_capacity = cache_capacity;
+        self
+    }
+
+    /// Add published IP ranges for a bot. Ranges for the same bot are merged.
+    pub fn add_ip_ranges(&mut self, name: BotName, ranges: IpRanges) {
+        self.ranges.entry(name).or_insert_with(|| IpRanges(vec![])).0.extend(ranges.0);
+    }
+
+    pub fn verify(&self, bot: &Bot, ip: IpAddr) -> Verification {
+        let key = (bot.name.clone(), ip);
+
+        if let Some(verification) = self.cache.lock().unwrap().get(&key, Instant::now()) {
+            return verification.clone()
+        }
+
+        let verification = self.verify_uncached(&bot.name, ip);
+        let ttl = match verification {
+            Verification::VerifiedHostname(_) | Verification::VerifiedIpRange => self.positive_ttl,
+            Verification::Failed => self.negative_ttl,
+            _ => return verification,
+        };
+        self.cache_insert(key, verification.clone(), ttl);
+
+        verification
+    }
+
+    fn cache_insert(&self, key: CacheKey, verification: Verification, ttl: Duration) {
+        if self.cache_capacity == 0 { return }
+
+        let now = Instant::now();
+        self.cache.lock().unwrap().insert(key, verification, now + ttl, now, self.cache_capacity)
+    }
+
+    /// Drop all cached results (eg. after reloading IP ranges)
+    pub fn clear_cache(&self) {
+        self.cache.lock().unwrap().clear()
+    }
+
+    fn verify_uncached(&self, name: &BotName, ip: IpAddr) -> Verification {
+        let ranges = self.ranges.get(name);
+        if ranges.is_some_and(|ranges| ranges.contains(&ip)) {
+            return Verification::VerifiedIpRange
+        }
+
+        let suffixes = hostname_suffixes(name);
+        if suffixes.is_empty() {
+            return if ranges.is_some() { Verification::Failed } else { Verification::Unverifiable }
+        }
+
+        let hostnames = match self.resolver.reverse(ip) {
+            Ok(hostnames) => hostnames,
+            Err(ref error) if error.kind() == io::ErrorKind::NotFound => return Verification::Failed,
+            Err(_) => return Verification::LookupFailed,
+        };
+
+        for hostname in hostnames {
+            if !suffixes.iter().any(|suffix| has_suffix(&hostname, suffix)) { continue }
+
+            match self.resolver.forward(&hostname) {
+                Ok(addresses) => {
+                    if addresses.contains(&ip) {
+                        return Verification::VerifiedHostname(hostname)
+                    }
+                },
+                Err(ref error) if error.kind() == io::ErrorKind::NotFound => (),
+                Err(_) => return Verification::LookupFailed,
+            }
+        }
+
+        Verification::Failed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::collections::HashMap;
+    use std::io;
+    use std::net::IpAddr;
+    use std::str::FromStr;
+    use std::time::Duration;
+
+    use bot::{Bot, BotName};
+    use super::{IpRange, IpRanges, Resolver, Verification, Verifier};
+
+    /// In-memory stand-in for DNS
+    struct MemoryResolver {
+        ptr: HashMap<IpAddr, String>,
+        a: HashMap<String, IpAddr>,
+        lookups: Cell<usize>,
+    }
+
+    impl Resolver for MemoryResolver {
+        fn reverse(&self, ip: IpAddr) -> io::Result<Vec<String>> {
+            self.lookups.set(self.lookups.get() + 1);
+            self.ptr.get(&ip)
+                .map(|hostname| vec![hostname.clone()])
+                .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no PTR record"))
+        }
+
+        fn forward(&self, hostname: &str) -> io::Result<Vec<IpAddr>> {
+            self.lookups.set(self.lookups.get() + 1);
+            self.a.get(hostname)
+                .map(|ip| vec![*ip])
+                .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no A record"))
+        }
+    }
+
+    fn ip(string: &str) -> IpAddr {
+        IpAddr::from_str(string).unwrap()
+    }
+
+    fn resolver() -> MemoryResolver {
+        let mut ptr = HashMap::new();
+        let mut a   = HashMap::new();
+
+        // Genuine Googlebot
+        ptr.insert(ip("66.249.66.1"), "crawl-66-249-66-1.googlebot.com".to_owned());
+        a.insert("crawl-66-249-66-1.googlebot.com".to_owned(), ip("66.249.66.1"));
+        // Spoofed PTR record that doesn't forward-confirm
+        ptr.insert(ip("203.0.113.9"), "crawl-1.googlebot.com".to_owned());
+        a.insert("crawl-1.googlebot.com".to_owned(), ip("66.249.66.2"));
+
+        MemoryResolver { ptr: ptr, a: a, lookups: Cell::new(0) }
+    }
+
+    #[test]
+    fn test_verify_googlebot() {
+        let verifier  = Verifier::new(resolver());
+        let googlebot = Bot::new(BotName::Googlebot);
+
+        assert_eq!(
+            verifier.verify(&googlebot, ip("66.249.66.1")),
+            Verification::VerifiedHostname("crawl-66-249-66-1.googlebot.com".to_owned())
+        );
+        assert_eq!(verifier.verify(&googlebot, ip("203.0.113.9")), Verification::Failed);
+        assert_eq!(verifier.verify(&googlebot, ip("198.51.100.1")), Verification::Failed);
+    }
+
+    #[test]
+    fn test_verify_with_ip_ranges() {
+        let mut verifier = Verifier::new(resolver());
+        let gptbot       = Bot::new(BotName::GPTBot);
+
+        assert_eq!(verifier.verify(&gptbot, ip("20.171.206.5")), Verification::Unverifiable);
+
+        let json = r#"{"creationTime": "2024-01-01", "prefixes": [{"ipv4Prefix": "20.171.206.0/24"}]}"#;
+        verifier.add_ip_ranges(BotName::GPTBot, IpRanges::parse(json).unwrap());
+
+        assert_eq!(verifier.verify(&gptbot, ip("20.171.206.5")), Verification::VerifiedIpRange);
+        assert_eq!(verifier.verify(&gptbot, ip("20.171.207.5")), Verification::Failed);
+    }
+
+    #[test]
+    fn test_cache_ttls() {
+        let googlebot = Bot::new(BotName::Googlebot);
+
+        let verifier = Verifier::new(resolver());
+        verifier.verify(&googlebot, ip("66.249.66.1"));
+        verifier.verify(&googlebot, ip("66.249.66.1"));
+        assert_eq!(verifier.resolver.lookups.get(), 2);
+
+        let verifier = Verifier::new(resolver()).with_ttls(Duration::from_secs(0), Duration::from_secs(0));
+        verifier.verify(&googlebot, ip("66.249.66.1"));
+        verifier.verify(&googlebot, ip("66.249.66.1"));
+        assert_eq!(verifier.resolver.lookups.get(), 4);
+    }
+
+    #[test]
+    fn test_cache_is_bounded() {
+        let googlebot = Bot::new(BotName::Googlebot);
+        let verifier  = Verifier::new(resolver()).with_cache_capacity(2);
+
+        verifier.verify(&googlebot, ip("66.249.66.1"));
+        for address in &["198.51.100.1", "198.51.100.2", "198.51.100.3"] {
+            verifier.verify(&googlebot, ip(address));
+        }
+        assert_eq!(verifier.cache.lock().unwrap().results.len(), 2);
+
+        // The verified result outlives the failures
+        let lookups = verifier.resolver.lookups.get();
+        verifier.verify(&googlebot, ip("66.249.66.1"));
+        assert_eq!(verifier.resolver.lookups.get(), lookups);
+
+        let expired = Verifier::new(resolver()).with_ttls(Duration::from_secs(0), Duration::from_secs(0));
+        for address in &["198.51.100.1", "198.51.100.2"] {
+            expired.verify(&googlebot, ip(address));
+        }
+        assert_eq!(expired.cache.lock().unwrap().results.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_ip_ranges() {
+        let ranges = IpRanges::parse("# Bingbot\n157.55.39.0/24\n\n2620:1ec:c11::/48\n").unwrap();
+
+        assert_eq!(ranges.0.len(), 2);
+        assert!(ranges.contains(&ip("157.55.39.200")));
+        assert!(ranges.contains(&ip("2620:1ec:c11::42")));
+        assert!(!ranges.contains(&ip("157.55.40.1")));
+
+        assert!(IpRange::from_str("10.0.0.0/33").is_err());
+        assert!(IpRanges::parse("not an address").is_err());
+    }
+}
@@ -0,0 +1,107 @@
+use std::str::FromStr;
+
+use platform::PlatformName;
+
+/// Marketing and code names for a platform version, from `data/platform_versions.tsv`
+#[derive(Clone, Debug, PartialEq)]
+pub struct VersionName {
+    pub platform: PlatformName,
+    pub major_version: i16,
+    /// `None` matches every minor version
+    pub minor_version: Option<i16>,
+    pub marketing_name: &'static str,
+    pub code_name: Option<&'static str>,
+    pub api_level: Option<u8>,
+    /// `YYYY-MM-DD`
+    pub release_date: Option<&'static str>,
+}
+
+const TABLE: &str = include_str!("../data/platform_versions.tsv");
+
+lazy_static! {
+    static ref VERSION_NAMES: Vec<VersionName> = parse(TABLE).unwrap();
+}
+
+fn optional(column: &'static str) -> Option<&'static str> {
+    if column == "-" { None } else { Some(column) }
+}
+
+fn parse(table: &'static str) -> Result<Vec<VersionName>, String> {
+    let mut names = vec![];
+
+    for (index, line) in table.lines().enumerate() {
+        if line.trim().is_empty() || line.starts_with('#') { continue }
+
+        let error = |message: &str| format!("line {}: {}", index + 1, message);
+
+        let columns: Vec<&'static str> = line.split('\t').collect();
+        if columns.len() != 6 { return Err(error("expected 6 tab-separated columns")) }
+
+        let platform = PlatformName::from_str(columns[0]).map_err(|_| error("unknown platform"))?;
+
+        let mut version = columns[1].splitn(2, '.');
+        let major = version.next().and_then(|v| i16::from_str(v).ok())
+            .ok_or_else(|| error("invalid version"))?;
+        let minor = match version.next() {
+            Some(minor) => Some(i16::from_str(minor).map_err(|_| error("invalid version"))?),
+            None => None,
+        };
+
+        let api_level = match optional(columns[4]) {
+            Some(level) => Some(u8::from_str(level).map_err(|_| error("invalid API level"))?),
+            None => None,
+        };
+
+        names.push(VersionName {
+            platform:       platform,
+            major_version:  major,
+            minor_version:  minor,
+            marketing_name: columns[2],
+            code_name:      optional(columns[3]),
+            api_level:      api_level,
+            release_date:   optional(columns[5]),
+        });
+    }
+
+    Ok(names)
+}
+
+pub fn lookup(platform: &PlatformName, major_version: i16, minor_version: i16) -> Option<&'static VersionName> {
+    VERSION_NAMES.iter().find(|name| {
+        name.platform == *platform &&
+            name.major_version == major_version &&
+            name.minor_version.is_none_or(|minor| minor == minor_version)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use platform::PlatformName::*;
+    use super::{lookup, parse, TABLE};
+
+    #[test]
+    fn test_bundled_table_parses() {
+        assert!(parse(TABLE).unwrap().len() > 50)
+    }
+
+    #[test]
+    fn test_lookup() {
+        let kitkat = lookup(&Android, 4, 4).unwrap();
+        assert_eq!((kitkat.code_name, kitkat.api_level), (Some("KitKat"), Some(19)));
+
+        assert_eq!(lookup(&Mac, 11, 6).unwrap().marketing_name, "macOS Big Sur");
+        assert_eq!(lookup(&Mac, 10, 9).unwrap().release_date, Some("2013-10-22"));
+        assert_eq!(lookup(&Windows, 8, 0).unwrap().marketing_name, "Windows 8");
+        assert_eq!(lookup(&IOS, 8, 0).unwrap().release_date, Some("2014-09-17"));
+        assert_eq!(lookup(&WindowsPhone, 7, 5).unwrap().code_name, Some("Mango"));
+        assert_eq!(lookup(&WindowsPhone, 7, 0).unwrap().marketing_name, "Windows Phone 7");
+        assert_eq!(lookup(&BlackBerry, 10, 2).unwrap().marketing_name, "BlackBerry 10");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("Android\t4.4\tAndroid KitKat\tKitKat\t19").is_err());
+        assert!(parse("Palm\t1.0\tPalm OS\t-\t-\t-").is_err());
+        assert!(parse("Android\tfour\tAndroid KitKat\tKitKat\t19\t-").is_err());
+    }
+}
@@ -0,0 +1,184 @@
+use regex::Regex;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WindowsRelease {
+    Windows2000,
+    WindowsXP,
+    WindowsXPx64,
+    Server2003,
+    Vista,
+    Windows7,
+    Windows8,
+    WindowsRT, // Windows 8 on ARM
+    Windows81,
+    WindowsRT81,
+    Windows10,
+    Windows11,
+}
+
+impl fmt::Display for WindowsRelease {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::WindowsRelease::*;
+
+        let name = match *self {
+            Windows2000  => "Windows 2000",
+            WindowsXP    => "Windows XP",
+            WindowsXPx64 => "Windows XP Professional x64",
+            Server2003   => "Windows Server 2003",
+            Vista        => "Windows Vista",
+            Windows7     => "Windows 7",
+            Windows8     => "Windows 8",
+            WindowsRT    => "Windows RT",
+            Windows81    => "Windows 8.1",
+            WindowsRT81  => "Windows RT 8.1",
+            Windows10    => "Windows 10",
+            Windows11    => "Windows 11",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A Windows version resolved from the `Windows NT x.y` token and, optionally, the
+/// `Sec-CH-UA-Platform-Version` client hint.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WindowsVersion {
+    pub nt_major_version: i16,
+    pub nt_minor_version: i16,
+    pub release: WindowsRelease,
+}
+
+lazy_static! {
+    static ref NT_REGEX: Regex  = Regex::new(r"Windows NT (\d+)\.(\d+)").unwrap();
+    static ref ARM_REGEX: Regex = Regex::new(r"\bARM\b").unwrap();
+    static ref X64_REGEX: Regex = Regex::new(r"\b(?:Win64|WOW64|x64)\b").unwrap();
+}
+
+impl WindowsVersion {
+    /// Resolve the release from the NT version in the user agent. Server editions share their
+    /// NT version with a client release, so only Server 2003 can be told apart: NT 5.2 is
+    /// treated as XP x64 when the user agent is 64-bit and Server 2003 otherwise.
+    pub fn parse(ua: &str) -> Option<WindowsVersion> {
+        use self::WindowsRelease::*;
+
+        let captures = NT_REGEX.captures(ua)?;
+        let major    = i16::from_str(captures.at(1)?).ok()?;
+        let minor    = i16::from_str(captures.at(2)?).ok()?;
+        let arm      = ARM_REGEX.is_match(ua);
+
+        let release = match (major, minor) {
+            (5, 0)           => Windows2000,
+            (5, 1)           => WindowsXP,
+            (5, 2)           => if X64_REGEX.is_match(ua) { WindowsXPx64 } else { Server2003 },
+            (6, 0)           => Vista,
+            (6, 1)           => Windows7,
+            (6, 2)           => if arm { WindowsRT } else { Windows8 },
+            (6, 3)           => if arm { WindowsRT81 } else { Windows81 },
+            (6, 4) | (10, 0) => Windows10, // NT 6.4 was only used by Windows 10 previews
+            _                => return None,
+        };
+
+        Some(WindowsVersion {
+            nt_major_version: major,
+            nt_minor_version: minor,
+            release:          release,
+        })
+    }
+
+    /// Windows 11 still sends `Windows NT 10.0`; only the `Sec-CH-UA-Platform-Version` hint
+    /// (13.0.0 or later) tells it apart from Windows 10.
+    pub fn resolve(ua: &str, platform_version: Option<&str>) -> Option<WindowsVersion> {
+        let mut version = WindowsVersion::parse(ua)?;
+        if let Some(hint) = platform_version {
+            version.apply_platform_version_hint(hint)
+        }
+
+        Some(version)
+    }
+
+    pub fn apply_platform_version_hint(&mut self, hint: &str) {
+        let major = hint.trim().trim_matches('"').split('.').next()
+            .and_then(|major| i16::from_str(major).ok());
+
+        if self.release == WindowsRelease::Windows10 && major.is_some_and(|major| major >= 13) {
+            self.release = WindowsRelease::Windows11
+        }
+    }
+
+    /// The version by which the release is known, in the form `Platform` reports it. Releases
+    /// before Windows 7 didn't have a version number in their name, so they keep their NT
+    /// version (XP is 5.1, Vista 6.0).
+    pub fn marketing_version(&self) -> (i16, i16) {
+        use self::WindowsRelease::*;
+
+        match self.release {
+            Windows2000 | WindowsXP | WindowsXPx64 | Server2003 | Vista => {
+                (self.nt_major_version, self.nt_minor_version)
+            },
+            Windows7                => (7, 0),
+            Windows8 | WindowsRT    => (8, 0),
+            Windows81 | WindowsRT81 => (8, 1),
+            Windows10               => (10, 0),
+            Windows11               => (11, 0),
+        }
+    }
+
+    pub fn is_server(&self) -> bool {
+        self.release == WindowsRelease::Server2003
+    }
+
+    /// Windows RT, the ARM-only edition of Windows 8 and 8.1
+    pub fn is_rt(&self) -> bool {
+        self.release == WindowsRelease::WindowsRT || self.release == WindowsRelease::WindowsRT81
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{WindowsRelease, WindowsVersion};
+    use super::WindowsRelease::*;
+
+    type StaticStr = &'static str;
+
+    const WINDOWS_2000: StaticStr = "Mozilla/5.0 (Windows NT 5.0; rv:10.0) Gecko/20100101 Firefox/10.0";
+    const SERVER_2003: StaticStr  = "Mozilla/5.0 (Windows NT 5.2; rv:10.0) Gecko/20100101 Firefox/10.0";
+    const XP_X64: StaticStr       = "Mozilla/5.0 (Windows NT 5.2; WOW64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/49.0.2623.112 Safari/537.36";
+    const SURFACE_RT: StaticStr   = "Mozilla/5.0 (compatible; MSIE 10.0; Windows NT 6.2; ARM; Trident/6.0; Touch)";
+    const PREVIEW: StaticStr      = "Mozilla/5.0 (Windows NT 6.4; WOW64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/36.0.1985.143 Safari/537.36 Edge/12.0";
+    const WINDOWS_10: StaticStr   = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+
+    fn release(ua: &str) -> WindowsRelease {
+        WindowsVersion::parse(ua).unwrap().release
+    }
+
+    #[test]
+    fn test_nt_versions() {
+        assert_eq!(release(WINDOWS_2000), Windows2000);
+        assert_eq!(release(SERVER_2003), Server2003);
+        assert_eq!(release(XP_X64), WindowsXPx64);
+        assert_eq!(release(PREVIEW), Windows10);
+        assert_eq!(WindowsVersion::parse("Mozilla/4.0 (compatible; MSIE 6.0; Windows NT 4.0)"), None);
+    }
+
+    #[test]
+    fn test_windows_rt() {
+        let rt = WindowsVersion::parse(SURFACE_RT).unwrap();
+
+        assert!(rt.is_rt());
+        assert_eq!(rt.marketing_version(), (8, 0));
+        assert_eq!(rt.release.to_string(), "Windows RT");
+    }
+
+    #[test]
+    fn test_windows_11_from_client_hint() {
+        let windows_11 = WindowsVersion::resolve(WINDOWS_10, Some("\"15.0.0\"")).unwrap();
+
+        assert_eq!(windows_11.release, Windows11);
+        assert_eq!((windows_11.nt_major_version, windows_11.nt_minor_version), (10, 0));
+        assert_eq!(windows_11.marketing_version(), (11, 0));
+
+        assert_eq!(WindowsVersion::resolve(WINDOWS_10, Some("10.0.0")).unwrap().release, Windows10);
+        assert_eq!(WindowsVersion::resolve(WINDOWS_10, None).unwrap().release, Windows10);
+    }
+}
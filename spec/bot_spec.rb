@@ -2,6 +2,10 @@ require 'spec_helper'
 
 describe FastBrowser do
   let(:googlebot) { 'Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)' }
+  let(:slackbot)  { 'Slackbot-LinkExpanding 1.0 (+https://api.slack.com/robots)' }
+  let(:newbot)    { 'Mozilla/5.0 (compatible; SomeNewBot/0.3; +https://example.com/bot)' }
+  let(:sqlmap)    { 'sqlmap/1.4.7#stable (http://sqlmap.org)' }
+  let(:gptbot)    { 'Mozilla/5.0 AppleWebKit/537.36 (KHTML, like Gecko); compatible; GPTBot/1.1; +https://openai.com/gptbot' }
 
   describe '#bot?' do
     it 'returns true for Googlebot' do
@@ -18,4 +22,61 @@ describe FastBrowser do
       expect(browser.bot_name).to eq 'Googlebot'
     end
   end
+
+  describe '#ai_crawler?' do
+    it 'returns true for GPTBot' do
+      browser = FastBrowser.new gptbot
+
+      expect(browser.ai_crawler?).to eq true
+    end
+
+    it 'returns false for Googlebot' do
+      browser = FastBrowser.new googlebot
+
+      expect(browser.ai_crawler?).to eq false
+    end
+  end
+
+  describe '#link_preview?' do
+    it 'returns true for Slackbot-LinkExpanding' do
+      browser = FastBrowser.new slackbot
+
+      expect(browser.link_preview?).to eq true
+    end
+
+    it 'returns false for Googlebot' do
+      browser = FastBrowser.new googlebot
+
+      expect(browser.link_preview?).to eq false
+    end
+  end
+
+  describe '#heuristic_bot?' do
+    it 'returns true for an uncatalogued crawler' do
+      browser = FastBrowser.new newbot
+
+      expect(browser.bot?).to eq true
+      expect(browser.heuristic_bot?).to eq true
+    end
+
+    it 'returns false for Googlebot' do
+      browser = FastBrowser.new googlebot
+
+      expect(browser.heuristic_bot?).to eq false
+    end
+  end
+
+  describe '#bot_category' do
+    it 'returns "Search" for Googlebot' do
+      browser = FastBrowser.new googlebot
+
+      expect(browser.bot_category).to eq 'Search'
+    end
+
+    it 'returns "SecurityScanner" for sqlmap' do
+      browser = FastBrowser.new sqlmap
+
+      expect(browser.bot_category).to eq 'SecurityScanner'
+    end
+  end
 end
@@ -0,0 +1,22 @@
+require 'spec_helper'
+
+describe FastBrowser do
+  let(:edge_html) { 'Mozilla/5.0 (Windows NT 10.0) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/42.0.2311.135 Safari/537.36 Edge/12.10136' }
+  let(:edge_chromium) { 'Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36 Edg/120.0.2210.91' }
+
+  it 'detects EdgeHTML as Edge' do
+    browser = FastBrowser.new edge_html
+
+    expect(browser.edge?).to eq true
+    expect(browser.chrome?).to eq false
+    expect(browser.major_version).to eq 12
+  end
+
+  it 'detects Chromium-based Edge as Chrome' do
+    browser = FastBrowser.new edge_chromium
+
+    expect(browser.chrome?).to eq true
+    expect(browser.edge?).to eq false
+    expect(browser.major_version).to eq 120
+  end
+end
@@ -0,0 +1,20 @@
+require 'spec_helper'
+
+describe FastBrowser do
+  let(:chrome)       { 'Mozilla/5.0 (Windows NT 6.1) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/41.0.2228.0 Safari/537.36' }
+  let(:chrome_on_xp)  { 'Mozilla/5.0 (Windows NT 5.1) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36' }
+
+  describe '#spoof_likelihood' do
+    it 'returns 0 for a consistent user agent' do
+      browser = FastBrowser.new chrome
+
+      expect(browser.spoof_likelihood).to eq 0.0
+    end
+
+    it 'returns more than 0 for Chrome 120 on Windows XP' do
+      browser = FastBrowser.new chrome_on_xp
+
+      expect(browser.spoof_likelihood).to be > 0.0
+    end
+  end
+end
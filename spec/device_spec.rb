@@ -0,0 +1,43 @@
+require 'spec_helper'
+
+describe FastBrowser do
+  let(:galaxy) { 'Mozilla/5.0 (Linux; Android 11; SM-G991B) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Mobile Safari/537.36' }
+  let(:ios_app) { 'MyApp/2.0 (iPhone12,1; iOS 14.2; Scale/2.00)' }
+  let(:chrome) { 'Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36' }
+
+  describe '#device_name' do
+    it 'returns the marketing name of an Android model' do
+      browser = FastBrowser.new galaxy
+
+      expect(browser.device_name).to eq 'Samsung Galaxy S21'
+      expect(browser.device_brand).to eq 'Samsung'
+    end
+
+    it 'returns Other for an unknown device' do
+      expect(FastBrowser.new(chrome).device_name).to eq 'Other'
+    end
+
+    it 'returns the marketing name and chip of an Apple hardware identifier' do
+      browser = FastBrowser.new ios_app
+
+      expect(browser.device_name).to eq 'Apple iPhone 11'
+      expect(browser.device_chip).to eq 'A13 Bionic'
+    end
+  end
+
+  describe '#mobile?' do
+    let(:android_tablet) { 'Mozilla/5.0 (Linux; Android 13; SM-X700) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36' }
+    let(:safari_mac) { 'Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.0 Safari/605.1.15' }
+
+    it 'is true for tablets' do
+      expect(FastBrowser.new(android_tablet).mobile?).to eq true
+    end
+
+    it 'is true for an iPad in desktop mode once touch is confirmed' do
+      browser = FastBrowser.new safari_mac
+      expect(browser.mobile?).to eq false
+
+      expect(browser.apply_client_signals(max_touch_points: 5).mobile?).to eq true
+    end
+  end
+end
@@ -0,0 +1,36 @@
+require 'spec_helper'
+
+describe FastBrowser do
+  let(:ios_app) { 'AppName/1.2.3 (iPhone12,1; iOS 14.2; Scale/3.00)' }
+  let(:pixel_7) { 'Mozilla/5.0 (Linux; Android 13; Pixel 7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/116.0.0.0 Mobile Safari/537.36' }
+  let(:chrome)  { 'Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36' }
+
+  describe '#scale' do
+    it 'returns the scale from the user agent' do
+      expect(FastBrowser.new(ios_app).scale).to eq 3.0
+    end
+
+    it 'uses client hints' do
+      browser = FastBrowser.new(chrome)
+        .apply_client_hint('Sec-CH-DPR', '2')
+        .apply_client_hint('Sec-CH-Viewport-Width', '1280')
+        .apply_client_hint('Sec-CH-Width', '640')
+
+      expect(browser.scale).to eq 2.0
+      expect(browser.viewport_width).to eq 1280
+      expect(browser.width).to eq 640
+    end
+  end
+
+  describe '#viewport_width' do
+    it 'defaults from the device model' do
+      expect(FastBrowser.new(pixel_7).viewport_width).to eq 412
+      expect(FastBrowser.new(ios_app).viewport_width).to eq 414
+    end
+
+    it 'returns 0 when unknown' do
+      expect(FastBrowser.new(chrome).viewport_width).to eq 0
+      expect(FastBrowser.new(chrome).width).to eq 0
+    end
+  end
+end
@@ -0,0 +1,32 @@
+require 'spec_helper'
+
+describe FastBrowser do
+  let(:curl)    { 'curl/7.68.0' }
+  let(:go)      { 'Go-http-client/1.1' }
+  let(:firefox) { 'Mozilla/5.0 (Windows NT 6.1; WOW64; rv:40.0) Gecko/20100101 Firefox/40.1' }
+
+  describe '#library?' do
+    it 'returns true for curl' do
+      browser = FastBrowser.new curl
+
+      expect(browser.library?).to eq true
+      expect(browser.bot?).to eq false
+    end
+
+    it 'returns false for Firefox' do
+      browser = FastBrowser.new firefox
+
+      expect(browser.library?).to eq false
+    end
+  end
+
+  describe '#library_name' do
+    it 'returns "Go-http-client" for Go' do
+      browser = FastBrowser.new go
+
+      expect(browser.library_name).to eq 'Go-http-client'
+      expect(browser.library_major_version).to eq 1
+      expect(browser.library_minor_version).to eq 1
+    end
+  end
+end
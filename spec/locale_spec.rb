@@ -0,0 +1,20 @@
+require 'spec_helper'
+
+describe FastBrowser do
+  let(:android) { 'Mozilla/5.0 (Linux; U; Android 4.0.3; ko-kr; LG-L160L Build/IML74K) AppleWebkit/534.30 (KHTML, like Gecko) Version/4.0 Mobile Safari/534.30' }
+  let(:chrome)  { 'Mozilla/5.0 (Windows NT 6.1) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/41.0.2228.0 Safari/537.36' }
+
+  describe '#locale' do
+    it 'returns the locale from the user agent' do
+      expect(FastBrowser.new(android).locale).to eq 'ko-KR'
+    end
+
+    it 'prefers the Accept-Language header' do
+      expect(FastBrowser.new(android).locale('en-GB, en;q=0.9')).to eq 'en-GB'
+    end
+
+    it 'returns nil without a locale' do
+      expect(FastBrowser.new(chrome).locale).to eq nil
+    end
+  end
+end
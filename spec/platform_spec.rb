@@ -0,0 +1,78 @@
+require 'spec_helper'
+
+describe FastBrowser do
+  let(:chrome_x64) { 'Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36' }
+  let(:mac)        { 'Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.0 Safari/605.1.15' }
+
+  describe '#architecture' do
+    it 'returns x64 for 64-bit Windows' do
+      browser = FastBrowser.new chrome_x64
+
+      expect(browser.architecture).to eq 'x64'
+      expect(browser.bitness).to eq 64
+    end
+
+    it 'prefers client hints' do
+      browser = FastBrowser.new(mac).apply_architecture_hints('"arm"', '"64"')
+
+      expect(browser.architecture).to eq 'ARM64'
+    end
+  end
+
+  describe '#platform_name' do
+    it 'returns the marketing name' do
+      mavericks = 'Mozilla/5.0 (Macintosh; Intel Mac OS X 10_9_3) AppleWebKit/537.75.14 (KHTML, like Gecko) Version/7.0.3 Safari/7046A194A'
+
+      expect(FastBrowser.new(mavericks).platform_name).to eq 'OS X Mavericks'
+    end
+
+    it "doesn't name a release for a frozen version" do
+      expect(FastBrowser.new(mac).platform_name).to eq 'macOS 10.15+'
+      expect(FastBrowser.new(chrome_x64).platform_name).to eq 'Windows 10+'
+    end
+  end
+
+  describe '#app_name' do
+    let(:ios_app) { 'MyApp/3.2.1 CFNetwork/1220.1 Darwin/20.3.0' }
+
+    it 'returns the app and its platform' do
+      browser = FastBrowser.new ios_app
+
+      expect(browser.app_name).to eq 'MyApp'
+      expect(browser.app_version).to eq '3.2.1'
+      expect(browser.platform_name).to eq 'iOS 14'
+    end
+
+    it "doesn't name the runtime as the app" do
+      browser = FastBrowser.new 'Dalvik/2.1.0 (Linux; U; Android 11; Pixel 5 Build/RQ3A.210805.001.A1)'
+
+      expect(browser.app_name).to eq 'Other'
+      expect(browser.app_version).to eq ''
+    end
+  end
+
+  describe '.register_app_format' do
+    it 'parses apps in the registered format' do
+      FastBrowser.register_app_format 'acme', '^Acme (?P<name>\\w+) v(?P<version>[\\d.]+)$'
+
+      browser = FastBrowser.new 'Acme Rockets v2.1'
+      expect(browser.app_name).to eq 'Rockets'
+      expect(browser.app_version).to eq '2.1'
+    end
+
+    it 'rejects invalid formats' do
+      expect { FastBrowser.register_app_format 'broken', '^(\\w+)/' }.to raise_error(ArgumentError)
+      expect { FastBrowser.register_app_format 'acme', '^(?P<name>\\w+)', 'BeOS' }.to raise_error(ArgumentError)
+    end
+  end
+
+  describe '#app_model' do
+    it 'returns the model and scale from an SDK user agent' do
+      browser = FastBrowser.new 'AppName/1.2.3 (iPhone; iOS 14.2; Scale/3.00)'
+
+      expect(browser.app_model).to eq 'iPhone'
+      expect(browser.app_scale).to eq 3.0
+      expect(browser.platform_name).to eq 'iOS 14.2'
+    end
+  end
+end
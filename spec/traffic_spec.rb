@@ -0,0 +1,31 @@
+require 'spec_helper'
+
+describe FastBrowser do
+  let(:chrome)    { 'Mozilla/5.0 (Windows NT 6.1) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/41.0.2228.0 Safari/537.36' }
+  let(:googlebot) { 'Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)' }
+  let(:headless)  { 'Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) HeadlessChrome/120.0.0.0 Safari/537.36' }
+
+  describe '#traffic_verdict' do
+    it 'returns Human for a browser' do
+      expect(FastBrowser.new(chrome).traffic_verdict).to eq 'Human'
+    end
+
+    it 'returns Human for a native app' do
+      expect(FastBrowser.new('MyApp/3.2.1 CFNetwork/1220.1 Darwin/20.3.0').traffic_verdict).to eq 'Human'
+    end
+
+    it 'returns ClaimedBot for an unverified Googlebot' do
+      expect(FastBrowser.new(googlebot).traffic_verdict).to eq 'ClaimedBot'
+    end
+
+    it 'returns Automation for headless Chrome' do
+      expect(FastBrowser.new(headless).traffic_verdict).to eq 'Automation'
+    end
+  end
+
+  describe '#traffic_score' do
+    it 'is higher for headless Chrome than for a browser' do
+      expect(FastBrowser.new(headless).traffic_score).to be > FastBrowser.new(chrome).traffic_score
+    end
+  end
+end